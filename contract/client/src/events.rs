@@ -0,0 +1,244 @@
+use crate::{
+    proxy::{
+        CommitmentStatus, CreatedEventData, DelegationEventData, DelegationKind, EventData,
+        ProofKind,
+    },
+    types::{bech32, DecodedCommitment},
+    EVENT_VERSION,
+};
+use multiversx_sc::codec::TopDecode;
+use multiversx_sc_scenario::{
+    imports::{Address, Bech32Address, StaticApi},
+    num_bigint::BigUint,
+};
+use std::fmt;
+
+/// A known event whose topics or data do not match this client's schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodeError(pub String);
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Fields shared by every transition, from the event's `EventData`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventMeta {
+    pub caller: Bech32Address,
+    pub timestamp: u64,
+    /// Status of the commitment after the transition.
+    pub status: CommitmentStatus,
+}
+
+/// The transition an event records, with its indexed topics decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    Created(Box<DecodedCommitment>),
+    ProofSubmitted { kind: ProofKind, reference: Vec<u8>, proof_hash: Vec<u8> },
+    ProofRevised { revision: u64, kind: ProofKind, reference: Vec<u8>, proof_hash: Vec<u8> },
+    OracleVerificationRequested { oracle: Bech32Address },
+    OracleVerified { verified: bool },
+    OracleVerificationFailed,
+    ProofSealed { proof_hash: Vec<u8> },
+    ProofRevealed { proof_url: Vec<u8>, proof_hash: Vec<u8> },
+    FailedFinalized { finalized_at: u64 },
+    Conceded,
+    Refunded { creator: Bech32Address, amount: BigUint },
+    Claimed { recipient: Bech32Address, amount: BigUint },
+    /// `release_to_recipient` sent the stake to a contract recipient and awaits its answer.
+    ReleaseRequested { recipient: Bech32Address, amount: BigUint },
+    /// A contract recipient rejected `release_to_recipient`; `reason` is its error message.
+    ReleaseFailed { recipient: Bech32Address, reason: String },
+    Swept { fallback: Bech32Address, amount: BigUint },
+    Forgiven { creator: Bech32Address, amount: BigUint },
+    Cancelled { refund: BigUint, penalty: BigUint },
+    /// The full record was dropped from contract storage; `record_hash` is the
+    /// sha256 of the top-encoded `Commitment` `get_commitment` last returned.
+    Pruned { record_hash: Vec<u8> },
+}
+
+/// One decoded event of the contract.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractEvent {
+    pub name: String,
+    pub id: u64,
+    pub meta: EventMeta,
+    pub change: Change,
+}
+
+/// Decodes a log entry emitted by the contract; `topics[0]` is the event
+/// identifier. Events the contract does not emit decode to `None`, known
+/// events with another layout or `version` are errors.
+pub fn decode_event(topics: &[Vec<u8>], data: &[u8]) -> Result<Option<ContractEvent>, DecodeError> {
+    let Some(identifier) = topics.first() else {
+        return Ok(None);
+    };
+    let name = String::from_utf8_lossy(identifier).into_owned();
+    let topics = Topics(topics);
+    let change = match name.as_str() {
+        "CommitmentCreated" => return decode_created(name, data).map(Some),
+        "ProofSubmitted" => Change::ProofSubmitted {
+            kind: topics.decode(2)?,
+            reference: topics.bytes(3)?,
+            proof_hash: topics.bytes(4)?,
+        },
+        "ProofRevised" => Change::ProofRevised {
+            revision: topics.decode(2)?,
+            kind: topics.decode(3)?,
+            reference: topics.bytes(4)?,
+            proof_hash: topics.bytes(5)?,
+        },
+        "OracleVerificationRequested" => {
+            Change::OracleVerificationRequested { oracle: topics.address(2)? }
+        },
+        "OracleVerified" => Change::OracleVerified { verified: topics.decode(2)? },
+        "OracleVerificationFailed" => Change::OracleVerificationFailed,
+        "ProofSealed" => Change::ProofSealed { proof_hash: topics.bytes(2)? },
+        "ProofRevealed" => Change::ProofRevealed {
+            proof_url: topics.bytes(2)?,
+            proof_hash: topics.bytes(3)?,
+        },
+        "FailedFinalized" => Change::FailedFinalized { finalized_at: topics.decode(2)? },
+        "Conceded" => Change::Conceded,
+        "Refunded" => Change::Refunded {
+            creator: topics.address(2)?,
+            amount: topics.big_uint(3)?,
+        },
+        "Claimed" => Change::Claimed {
+            recipient: topics.address(2)?,
+            amount: topics.big_uint(3)?,
+        },
+        "ReleaseRequested" => Change::ReleaseRequested {
+            recipient: topics.address(2)?,
+            amount: topics.big_uint(3)?,
+        },
+        "ReleaseFailed" => Change::ReleaseFailed {
+            recipient: topics.address(2)?,
+            reason: String::from_utf8_lossy(&topics.bytes(3)?).into_owned(),
+        },
+        "Swept" => Change::Swept {
+            fallback: topics.address(2)?,
+            amount: topics.big_uint(3)?,
+        },
+        "Forgiven" => Change::Forgiven {
+            creator: topics.address(2)?,
+            amount: topics.big_uint(3)?,
+        },
+        "Cancelled" => Change::Cancelled {
+            refund: topics.big_uint(2)?,
+            penalty: topics.big_uint(3)?,
+        },
+        "CommitmentPruned" => Change::Pruned { record_hash: topics.bytes(2)? },
+        _ => return Ok(None),
+    };
+
+    let data = EventData::<StaticApi>::top_decode(data)
+        .map_err(|err| DecodeError(format!("{name}: invalid event data: {err:?}")))?;
+    check_version(&name, data.version)?;
+
+    Ok(Some(ContractEvent {
+        id: topics.decode(1)?,
+        name,
+        meta: EventMeta {
+            caller: bech32(&data.caller.to_address()),
+            timestamp: data.timestamp,
+            status: data.status,
+        },
+        change,
+    }))
+}
+
+/// `DelegateAdded` or `DelegateRemoved`. They change no commitment, so
+/// [`decode_event`] skips them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DelegationEvent {
+    pub owner: Bech32Address,
+    pub kind: DelegationKind,
+    pub delegate: Bech32Address,
+    /// `false` when the delegation was revoked.
+    pub active: bool,
+    pub timestamp: u64,
+}
+
+/// Decodes a delegation log entry; any other event decodes to `None`.
+pub fn decode_delegation_event(
+    topics: &[Vec<u8>],
+    data: &[u8],
+) -> Result<Option<DelegationEvent>, DecodeError> {
+    let active = match topics.first().map(Vec::as_slice) {
+        Some(b"DelegateAdded") => true,
+        Some(b"DelegateRemoved") => false,
+        _ => return Ok(None),
+    };
+    let name = if active { "DelegateAdded" } else { "DelegateRemoved" };
+    let topics = Topics(topics);
+
+    let data = DelegationEventData::top_decode(data)
+        .map_err(|err| DecodeError(format!("{name}: invalid event data: {err:?}")))?;
+    check_version(name, data.version)?;
+
+    Ok(Some(DelegationEvent {
+        owner: topics.address(1)?,
+        kind: topics.decode(2)?,
+        delegate: topics.address(3)?,
+        active,
+        timestamp: data.timestamp,
+    }))
+}
+
+fn decode_created(name: String, data: &[u8]) -> Result<ContractEvent, DecodeError> {
+    let data = CreatedEventData::<StaticApi>::top_decode(data)
+        .map_err(|err| DecodeError(format!("{name}: invalid event data: {err:?}")))?;
+    check_version(&name, data.version)?;
+
+    let commitment = DecodedCommitment::from(&data.commitment);
+    Ok(ContractEvent {
+        name,
+        id: commitment.id,
+        meta: EventMeta {
+            caller: commitment.creator.clone(),
+            timestamp: commitment.created_at,
+            status: commitment.status,
+        },
+        change: Change::Created(Box::new(commitment)),
+    })
+}
+
+fn check_version(name: &str, version: u8) -> Result<(), DecodeError> {
+    if version != EVENT_VERSION {
+        return Err(DecodeError(format!("{name}: unsupported event version {version}")));
+    }
+    Ok(())
+}
+
+struct Topics<'a>(&'a [Vec<u8>]);
+
+impl Topics<'_> {
+    fn bytes(&self, index: usize) -> Result<Vec<u8>, DecodeError> {
+        self.0
+            .get(index)
+            .cloned()
+            .ok_or_else(|| DecodeError(format!("missing topic {index}")))
+    }
+
+    fn decode<T: TopDecode>(&self, index: usize) -> Result<T, DecodeError> {
+        T::top_decode(self.bytes(index)?.as_slice())
+            .map_err(|err| DecodeError(format!("invalid topic {index}: {err:?}")))
+    }
+
+    fn address(&self, index: usize) -> Result<Bech32Address, DecodeError> {
+        let bytes = self.bytes(index)?;
+        if bytes.len() != 32 {
+            return Err(DecodeError(format!("invalid address in topic {index}")));
+        }
+        Ok(bech32(&Address::from_slice(&bytes)))
+    }
+
+    fn big_uint(&self, index: usize) -> Result<BigUint, DecodeError> {
+        Ok(BigUint::from_bytes_be(&self.bytes(index)?))
+    }
+}
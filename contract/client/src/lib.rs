@@ -0,0 +1,15 @@
+pub mod events;
+pub mod proxy;
+pub mod types;
+
+pub use events::{
+    decode_delegation_event, decode_event, ContractEvent, DecodeError, DelegationEvent,
+};
+pub use proxy::{CommitmentStatus, DelegationKind, ProofKind, PublicCommitmentFundProxy};
+pub use types::{parse_address, DecodedCommitment};
+
+/// Event schema version this client decodes; mirrors the contract's `EVENT_VERSION`.
+pub const EVENT_VERSION: u8 = 1;
+
+/// Mirrors the contract's `REVEAL_WINDOW_SECONDS`.
+pub const REVEAL_WINDOW_SECONDS: u64 = 86_400;
@@ -0,0 +1,866 @@
+// Code generated by the multiversx-sc proxy generator. DO NOT EDIT.
+
+////////////////////////////////////////////////////
+////////////////// AUTO-GENERATED //////////////////
+////////////////////////////////////////////////////
+
+#![allow(dead_code)]
+#![allow(clippy::all)]
+
+use multiversx_sc::proxy_imports::*;
+
+pub struct PublicCommitmentFundProxy;
+
+impl<Env, From, To, Gas> TxProxyTrait<Env, From, To, Gas> for PublicCommitmentFundProxy
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    type TxProxyMethods = PublicCommitmentFundProxyMethods<Env, From, To, Gas>;
+
+    fn proxy_methods(self, tx: Tx<Env, From, To, (), Gas, (), ()>) -> Self::TxProxyMethods {
+        PublicCommitmentFundProxyMethods { wrapped_tx: tx }
+    }
+}
+
+pub struct PublicCommitmentFundProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    wrapped_tx: Tx<Env, From, To, (), Gas, (), ()>,
+}
+
+#[rustfmt::skip]
+impl<Env, From, Gas> PublicCommitmentFundProxyMethods<Env, From, (), Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn init(
+        self,
+    ) -> TxTypedDeploy<Env, From, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_deploy()
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> PublicCommitmentFundProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    pub fn upgrade(
+        self,
+    ) -> TxTypedUpgrade<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_upgrade()
+            .original_result()
+    }
+}
+
+#[rustfmt::skip]
+impl<Env, From, To, Gas> PublicCommitmentFundProxyMethods<Env, From, To, Gas>
+where
+    Env: TxEnv,
+    Env::Api: VMApi,
+    From: TxFrom<Env>,
+    To: TxTo<Env>,
+    Gas: TxGas<Env>,
+{
+    /// Re-encodes commitments still stored in an older layout and indexes the 
+    /// ones that can still pay out. Ids already on the current layout are skipped. 
+    pub fn migrate_commitments<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, u64>>,
+    >(
+        self,
+        ids: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("migrate_commitments")
+            .argument(&ids)
+            .original_result()
+    }
+
+    /// How long a settled commitment is kept in full before it may be pruned. 
+    pub fn set_archive_retention_seconds<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        seconds: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("set_archive_retention_seconds")
+            .argument(&seconds)
+            .original_result()
+    }
+
+    /// Replaces settled commitments (refunded, claimed, swept or forgiven) whose 
+    /// retention period has passed with an `ArchivedCommitment`, dropping their 
+    /// proof history and tag index entries. Callable by anyone; ids that are not 
+    /// prunable yet are skipped. 
+    pub fn prune_commitments<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, u64>>,
+    >(
+        self,
+        ids: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("prune_commitments")
+            .argument(&ids)
+            .original_result()
+    }
+
+    /// Restricts `create_commitment` tags to the owner-curated vocabulary when 
+    /// enabled; otherwise any well-formed tag is accepted. 
+    pub fn set_curated_tags_only<
+        Arg0: ProxyArg<bool>,
+    >(
+        self,
+        curated: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("set_curated_tags_only")
+            .argument(&curated)
+            .original_result()
+    }
+
+    pub fn add_allowed_tags<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>>,
+    >(
+        self,
+        tags: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("add_allowed_tags")
+            .argument(&tags)
+            .original_result()
+    }
+
+    pub fn remove_allowed_tags<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>>,
+    >(
+        self,
+        tags: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("remove_allowed_tags")
+            .argument(&tags)
+            .original_result()
+    }
+
+    pub fn create_commitment<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg2: ProxyArg<u64>,
+        Arg3: ProxyArg<OptionalValue<CreateOptions<Env::Api>>>,
+    >(
+        self,
+        title: Arg0,
+        recipient: Arg1,
+        deadline: Arg2,
+        options_opt: Arg3,
+    ) -> TxTypedCall<Env, From, To, (), Gas, ()> {
+        self.wrapped_tx
+            .raw_call("create_commitment")
+            .argument(&title)
+            .argument(&recipient)
+            .argument(&deadline)
+            .argument(&options_opt)
+            .original_result()
+    }
+
+    pub fn submit_proof<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        id: Arg0,
+        proof_url: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("submit_proof")
+            .argument(&id)
+            .argument(&proof_url)
+            .original_result()
+    }
+
+    /// Submits a typed proof. `reference` is validated against `kind`, and 
+    /// `evidence_hash` is the creator's own hash of the evidence it points at. 
+    pub fn submit_typed_proof<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ProofKind>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg3: ProxyArg<ManagedByteArray<Env::Api, 32usize>>,
+    >(
+        self,
+        id: Arg0,
+        kind: Arg1,
+        reference: Arg2,
+        evidence_hash: Arg3,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("submit_typed_proof")
+            .argument(&id)
+            .argument(&kind)
+            .argument(&reference)
+            .argument(&evidence_hash)
+            .original_result()
+    }
+
+    /// Submits a proof URL together with the attester's ed25519 signature over 
+    /// `get_attestation_message(id, proof_url)`. Required when an attester is set. 
+    pub fn submit_attested_proof<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<ManagedByteArray<Env::Api, 64usize>>,
+    >(
+        self,
+        id: Arg0,
+        proof_url: Arg1,
+        signature: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("submit_attested_proof")
+            .argument(&id)
+            .argument(&proof_url)
+            .argument(&signature)
+            .original_result()
+    }
+
+    /// Asks the commitment's oracle contract whether the goal was met. The 
+    /// callback completes the commitment on `true` and fails it on `false`. 
+    pub fn request_oracle_verification<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("request_oracle_verification")
+            .argument(&id)
+            .original_result()
+    }
+
+    /// Commits to a proof without publishing it: `proof_hash` is 
+    /// `sha256(proof_url ++ salt)`, revealed with `reveal_proof` after the deadline. 
+    pub fn submit_sealed_proof<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedByteArray<Env::Api, 32usize>>,
+    >(
+        self,
+        id: Arg0,
+        proof_hash: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("submit_sealed_proof")
+            .argument(&id)
+            .argument(&proof_hash)
+            .original_result()
+    }
+
+    /// Publishes a sealed proof. Only accepted after the deadline and within the 
+    /// reveal window; an unrevealed commitment fails once the window closes. 
+    pub fn reveal_proof<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg2: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        id: Arg0,
+        proof_url: Arg1,
+        salt: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("reveal_proof")
+            .argument(&id)
+            .argument(&proof_url)
+            .argument(&salt)
+            .original_result()
+    }
+
+    pub fn finalize<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("finalize")
+            .argument(&id)
+            .original_result()
+    }
+
+    pub fn claim<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claim")
+            .argument(&id)
+            .original_result()
+    }
+
+    /// Claims the failed commitments of the caller whose cooldown has elapsed, 
+    /// paying the sum out in a single transfer. Looks at `limit` of the 
+    /// caller's open commitments, from position `start` (0 by default) of 
+    /// `get_total_open_ids`, so commitments that are not claimable yet cannot 
+    /// make the call run out of gas. Expired commitments nobody finalized are 
+    /// settled along the way. 
+    pub fn claim_all<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<u64>>,
+    >(
+        self,
+        limit: Arg0,
+        start_opt: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("claim_all")
+            .argument(&limit)
+            .argument(&start_opt)
+            .original_result()
+    }
+
+    /// Pays a claimable stake out to its recipient on their behalf. Callable by 
+    /// anyone. Contract recipients are paid with an async call, to the endpoint 
+    /// they registered with `set_release_endpoint` if any, so a rejected 
+    /// payment leaves the stake claimable instead of failing the transaction. 
+    pub fn release_to_recipient<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("release_to_recipient")
+            .argument(&id)
+            .original_result()
+    }
+
+    /// Sets, or clears when omitted, the endpoint `release_to_recipient` calls 
+    /// on the calling contract with the stake and the commitment id. 
+    pub fn set_release_endpoint<
+        Arg0: ProxyArg<OptionalValue<ManagedBuffer<Env::Api>>>,
+    >(
+        self,
+        endpoint_opt: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("set_release_endpoint")
+            .argument(&endpoint_opt)
+            .original_result()
+    }
+
+    /// Sends a forfeited stake the recipient never claimed to the commitment's 
+    /// fallback address once its claim window has expired. Callable by anyone. 
+    pub fn sweep<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("sweep")
+            .argument(&id)
+            .original_result()
+    }
+
+    /// Lets the recipient of a failed commitment waive `amount` of the forfeited 
+    /// stake (all of it by default), refunding it to the creator straight away. 
+    pub fn forgive<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<OptionalValue<BigUint<Env::Api>>>,
+    >(
+        self,
+        id: Arg0,
+        amount_opt: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("forgive")
+            .argument(&id)
+            .argument(&amount_opt)
+            .original_result()
+    }
+
+    /// Lets the creator walk away before the deadline. Free within the grace 
+    /// period after creation; afterwards `cancel_penalty_bps` of the stake goes 
+    /// to the recipient and the rest is refunded. 
+    pub fn cancel<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("cancel")
+            .argument(&id)
+            .original_result()
+    }
+
+    /// Lets the creator give up on an active or sealed commitment that can 
+    /// still complete, without waiting for it to lapse: it fails now, and the 
+    /// claim cooldown starts now. 
+    pub fn concede<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("concede")
+            .argument(&id)
+            .original_result()
+    }
+
+    /// The compact record of a pruned commitment; empty for ids never pruned. 
+    pub fn get_archived_commitment<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, OptionalValue<ArchivedCommitment<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_archived_commitment")
+            .argument(&id)
+            .original_result()
+    }
+
+    pub fn is_pruned<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("is_pruned")
+            .argument(&id)
+            .original_result()
+    }
+
+    pub fn get_archive_retention_seconds(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_archive_retention_seconds")
+            .original_result()
+    }
+
+    pub fn get_commitment<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, Commitment<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_commitment")
+            .argument(&id)
+            .original_result()
+    }
+
+    /// Every proof revision of a commitment, oldest first; the last entry is the 
+    /// one reflected in `get_commitment`. 
+    pub fn get_proof_history<
+        Arg0: ProxyArg<u64>,
+    >(
+        self,
+        id: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ProofEntry<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_proof_history")
+            .argument(&id)
+            .original_result()
+    }
+
+    /// Bytes an attester signs to vouch for `proof_url` on commitment `id`. 
+    pub fn get_attestation_message<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        id: Arg0,
+        proof_url: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_attestation_message")
+            .argument(&id)
+            .argument(&proof_url)
+            .original_result()
+    }
+
+    pub fn get_release_endpoint<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        recipient: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ManagedBuffer<Env::Api>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_release_endpoint")
+            .argument(&recipient)
+            .original_result()
+    }
+
+    /// Ids are assigned sequentially from 1, so every id below `next_id` exists, 
+    /// pruned ones included. 
+    pub fn get_total_ids(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_total_ids")
+            .original_result()
+    }
+
+    pub fn get_ids_page<
+        Arg0: ProxyArg<u64>,
+        Arg1: ProxyArg<u64>,
+    >(
+        self,
+        start: Arg0,
+        limit: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_ids_page")
+            .argument(&start)
+            .argument(&limit)
+            .original_result()
+    }
+
+    /// Number of commitments paying out to `recipient` that are not settled 
+    /// yet, the positions `claim_all` pages over. 
+    pub fn get_total_open_ids<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        recipient: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_total_open_ids")
+            .argument(&recipient)
+            .original_result()
+    }
+
+    /// Ids of stored commitments created with `tag`, paginated like `get_ids_page`. 
+    /// Oldest first, except that pruning moves the newest id into the pruned one's place. 
+    pub fn get_ids_by_tag<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+        Arg1: ProxyArg<u64>,
+        Arg2: ProxyArg<u64>,
+    >(
+        self,
+        tag: Arg0,
+        start: Arg1,
+        limit: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, u64>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_ids_by_tag")
+            .argument(&tag)
+            .argument(&start)
+            .argument(&limit)
+            .original_result()
+    }
+
+    pub fn get_total_ids_by_tag<
+        Arg0: ProxyArg<ManagedBuffer<Env::Api>>,
+    >(
+        self,
+        tag: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, u64> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_total_ids_by_tag")
+            .argument(&tag)
+            .original_result()
+    }
+
+    pub fn is_curated_tags_only(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("is_curated_tags_only")
+            .original_result()
+    }
+
+    pub fn get_allowed_tags(
+        self,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedBuffer<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_allowed_tags")
+            .original_result()
+    }
+
+    /// The commitments of `ids` still stored, in the order asked. Unknown and 
+    /// pruned ids are left out; `is_pruned` tells them apart. 
+    pub fn get_commitments_batch<
+        Arg0: ProxyArg<MultiValueEncoded<Env::Api, u64>>,
+    >(
+        self,
+        ids: Arg0,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, Commitment<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_commitments_batch")
+            .argument(&ids)
+            .original_result()
+    }
+
+    pub fn add_delegate<
+        Arg0: ProxyArg<DelegationKind>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        kind: Arg0,
+        delegate: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("add_delegate")
+            .argument(&kind)
+            .argument(&delegate)
+            .original_result()
+    }
+
+    pub fn remove_delegate<
+        Arg0: ProxyArg<DelegationKind>,
+        Arg1: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        kind: Arg0,
+        delegate: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, ()> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("remove_delegate")
+            .argument(&kind)
+            .argument(&delegate)
+            .original_result()
+    }
+
+    pub fn get_delegates<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<DelegationKind>,
+    >(
+        self,
+        owner: Arg0,
+        kind: Arg1,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, MultiValueEncoded<Env::Api, ManagedAddress<Env::Api>>> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("get_delegates")
+            .argument(&owner)
+            .argument(&kind)
+            .original_result()
+    }
+
+    pub fn is_delegate<
+        Arg0: ProxyArg<ManagedAddress<Env::Api>>,
+        Arg1: ProxyArg<DelegationKind>,
+        Arg2: ProxyArg<ManagedAddress<Env::Api>>,
+    >(
+        self,
+        owner: Arg0,
+        kind: Arg1,
+        delegate: Arg2,
+    ) -> TxTypedCall<Env, From, To, NotPayable, Gas, bool> {
+        self.wrapped_tx
+            .payment(NotPayable)
+            .raw_call("is_delegate")
+            .argument(&owner)
+            .argument(&kind)
+            .argument(&delegate)
+            .original_result()
+    }
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct CreateOptions<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub cooldown_seconds: Option<u64>,
+    pub claim_window_seconds: Option<u64>,
+    pub fallback: Option<ManagedAddress<Api>>,
+    pub oracle: Option<ManagedAddress<Api>>,
+    pub attester: Option<ManagedAddress<Api>>,
+    pub start_at: Option<u64>,
+    pub cancel_grace_seconds: Option<u64>,
+    pub cancel_penalty_bps: Option<u64>,
+    pub cancellable: Option<bool>,
+    pub tags: Option<ManagedVec<Api, ManagedBuffer<Api>>>,
+    pub description: Option<ManagedBuffer<Api>>,
+    pub metadata_uri: Option<ManagedBuffer<Api>>,
+    pub metadata_hash: Option<ManagedByteArray<Api, 32usize>>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProofKind {
+    HttpUrl,
+    IpfsCid,
+    ContentHash,
+    TxHash,
+    OracleAttestation,
+    AttestedUrl,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct ArchivedCommitment<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub id: u64,
+    pub creator: ManagedAddress<Api>,
+    pub recipient: ManagedAddress<Api>,
+    pub amount: BigUint<Api>,
+    pub status: CommitmentStatus,
+    pub settled_at: u64,
+    pub record_hash: ManagedBuffer<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CommitmentStatus {
+    Active,
+    Completed,
+    Failed,
+    Refunded,
+    Claimed,
+    Swept,
+    Forgiven,
+    Sealed,
+    Verifying,
+    Releasing,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, PartialEq, Debug)]
+pub struct Commitment<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub id: u64,
+    pub creator: ManagedAddress<Api>,
+    pub recipient: ManagedAddress<Api>,
+    pub amount: BigUint<Api>,
+    pub deadline: u64,
+    pub cooldown_seconds: u64,
+    pub created_at: u64,
+    pub status: CommitmentStatus,
+    pub title: ManagedBuffer<Api>,
+    pub proof_url: ManagedBuffer<Api>,
+    pub proof_hash: ManagedBuffer<Api>,
+    pub proof_submitted_at: u64,
+    pub finalized_at: u64,
+    pub fallback: ManagedAddress<Api>,
+    pub claim_window_seconds: u64,
+    pub forgiven_amount: BigUint<Api>,
+    pub proof_kind: ProofKind,
+    pub oracle: ManagedAddress<Api>,
+    pub attester: ManagedAddress<Api>,
+    pub start_at: u64,
+    pub cancel_grace_seconds: u64,
+    pub cancel_penalty_bps: u64,
+    pub cancellable: bool,
+    pub tags: ManagedVec<Api, ManagedBuffer<Api>>,
+    pub description: ManagedBuffer<Api>,
+    pub metadata_uri: ManagedBuffer<Api>,
+    pub metadata_hash: ManagedBuffer<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, PartialEq, Debug)]
+pub struct ProofEntry<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub kind: ProofKind,
+    pub reference: ManagedBuffer<Api>,
+    pub proof_hash: ManagedBuffer<Api>,
+    pub submitted_at: u64,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct CreatedEventData<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub version: u8,
+    pub commitment: Commitment<Api>,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct EventData<Api>
+where
+    Api: ManagedTypeApi,
+{
+    pub version: u8,
+    pub caller: ManagedAddress<Api>,
+    pub timestamp: u64,
+    pub status: CommitmentStatus,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DelegationKind {
+    Claim,
+    Proof,
+}
+
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct DelegationEventData {
+    pub version: u8,
+    pub timestamp: u64,
+}
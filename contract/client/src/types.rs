@@ -0,0 +1,113 @@
+use crate::proxy::{Commitment, CommitmentStatus, ProofKind};
+use multiversx_sc::types::{BigUint, ManagedBuffer};
+use multiversx_sc_scenario::{
+    imports::{Address, Bech32Address, StaticApi},
+    num_bigint,
+};
+
+/// `Commitment` with every managed type converted to a plain Rust value, so it
+/// can be stored, sent across threads or printed without a VM context.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedCommitment {
+    pub id: u64,
+    pub creator: Bech32Address,
+    pub recipient: Bech32Address,
+    pub amount: num_bigint::BigUint,
+    pub deadline: u64,
+    pub cooldown_seconds: u64,
+    pub created_at: u64,
+    pub status: CommitmentStatus,
+    pub title: String,
+    pub proof_url: Vec<u8>,
+    pub proof_hash: Vec<u8>,
+    pub proof_submitted_at: u64,
+    pub finalized_at: u64,
+    pub fallback: Bech32Address,
+    pub claim_window_seconds: u64,
+    pub forgiven_amount: num_bigint::BigUint,
+    pub proof_kind: ProofKind,
+    /// `None` when the commitment has no oracle.
+    pub oracle: Option<Bech32Address>,
+    /// `None` when the commitment has no attester.
+    pub attester: Option<Bech32Address>,
+    pub start_at: u64,
+    pub cancel_grace_seconds: u64,
+    pub cancel_penalty_bps: u64,
+    pub cancellable: bool,
+    pub tags: Vec<String>,
+    pub description: String,
+    pub metadata_uri: String,
+    pub metadata_hash: Vec<u8>,
+}
+
+impl From<&Commitment<StaticApi>> for DecodedCommitment {
+    fn from(commitment: &Commitment<StaticApi>) -> Self {
+        DecodedCommitment {
+            id: commitment.id,
+            creator: bech32(&commitment.creator.to_address()),
+            recipient: bech32(&commitment.recipient.to_address()),
+            amount: big_uint(&commitment.amount),
+            deadline: commitment.deadline,
+            cooldown_seconds: commitment.cooldown_seconds,
+            created_at: commitment.created_at,
+            status: commitment.status,
+            title: utf8(&commitment.title),
+            proof_url: bytes(&commitment.proof_url),
+            proof_hash: bytes(&commitment.proof_hash),
+            proof_submitted_at: commitment.proof_submitted_at,
+            finalized_at: commitment.finalized_at,
+            fallback: bech32(&commitment.fallback.to_address()),
+            claim_window_seconds: commitment.claim_window_seconds,
+            forgiven_amount: big_uint(&commitment.forgiven_amount),
+            proof_kind: commitment.proof_kind,
+            oracle: optional_address(&commitment.oracle.to_address()),
+            attester: optional_address(&commitment.attester.to_address()),
+            start_at: commitment.start_at,
+            cancel_grace_seconds: commitment.cancel_grace_seconds,
+            cancel_penalty_bps: commitment.cancel_penalty_bps,
+            cancellable: commitment.cancellable,
+            tags: commitment.tags.iter().map(|tag| utf8(&tag)).collect(),
+            description: utf8(&commitment.description),
+            metadata_uri: utf8(&commitment.metadata_uri),
+            metadata_hash: bytes(&commitment.metadata_hash),
+        }
+    }
+}
+
+impl DecodedCommitment {
+    /// Amount still owed to the recipient once forgiven parts are subtracted.
+    pub fn remaining_amount(&self) -> num_bigint::BigUint {
+        &self.amount - &self.forgiven_amount
+    }
+}
+
+/// Parses a bech32 address. Unlike `Bech32Address::from_bech32_string`, bad
+/// input is an error rather than a panic.
+pub fn parse_address(address: &str) -> Result<Bech32Address, String> {
+    let (_, bytes) = ::bech32::decode(address)
+        .map_err(|err| format!("invalid bech32 address `{address}`: {err}"))?;
+    if bytes.len() != 32 {
+        return Err(format!("invalid bech32 address `{address}`: not 32 bytes"));
+    }
+    Ok(Bech32Address::from_bech32_string(address.to_owned()))
+}
+
+pub(crate) fn bech32(address: &Address) -> Bech32Address {
+    Bech32Address::from(address)
+}
+
+fn optional_address(address: &Address) -> Option<Bech32Address> {
+    (!address.is_zero()).then(|| bech32(address))
+}
+
+pub(crate) fn big_uint(value: &BigUint<StaticApi>) -> num_bigint::BigUint {
+    num_bigint::BigUint::from_bytes_be(value.to_bytes_be().as_slice())
+}
+
+pub(crate) fn bytes(buffer: &ManagedBuffer<StaticApi>) -> Vec<u8> {
+    buffer.to_boxed_bytes().into_vec()
+}
+
+pub(crate) fn utf8(buffer: &ManagedBuffer<StaticApi>) -> String {
+    String::from_utf8_lossy(buffer.to_boxed_bytes().as_slice()).into_owned()
+}
@@ -0,0 +1,167 @@
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_scenario::imports::*;
+use public_commitment_fund::{Commitment, CreatedEventData, EventData};
+use public_commitment_fund_client::{
+    decode_delegation_event, decode_event,
+    events::Change,
+    parse_address,
+    CommitmentStatus, DelegationKind, ProofKind, EVENT_VERSION, REVEAL_WINDOW_SECONDS,
+};
+use serde_json::Value;
+
+const PROXY: &str = include_str!("../src/proxy.rs");
+const ABI: &str = include_str!("../../../frontend/contracts/public-commitment-fund.abi.json");
+
+fn abi_names(section: &str, key: &str) -> Vec<String> {
+    let abi: Value = serde_json::from_str(ABI).unwrap();
+    abi[section]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry[key].as_str().unwrap().to_owned())
+        .collect()
+}
+
+fn address(byte: u8) -> Address {
+    Address::from([byte; 32])
+}
+
+#[test]
+fn constants_match_contract() {
+    assert_eq!(EVENT_VERSION, public_commitment_fund::EVENT_VERSION);
+    assert_eq!(REVEAL_WINDOW_SECONDS, public_commitment_fund::REVEAL_WINDOW_SECONDS);
+}
+
+#[test]
+fn proxy_and_decoders_cover_the_abi() {
+    for endpoint in abi_names("endpoints", "name") {
+        assert!(
+            PROXY.contains(&format!(".raw_call(\"{endpoint}\")")),
+            "proxy is missing `{endpoint}`; run `cargo run -- proxy` in contract/meta"
+        );
+    }
+
+    // A known identifier with no payload is a decode error, never "unknown".
+    for event in abi_names("events", "identifier") {
+        let topics = vec![event.as_bytes().to_vec()];
+        assert!(
+            decode_event(&topics, &[]).is_err() || decode_delegation_event(&topics, &[]).is_err(),
+            "no decoder for `{event}`"
+        );
+    }
+}
+
+#[test]
+fn decodes_contract_events() {
+    let commitment = Commitment::<StaticApi> {
+        id: 7,
+        creator: address(1).into(),
+        recipient: address(2).into(),
+        amount: BigUint::from(5_000u64),
+        deadline: 2_000,
+        cooldown_seconds: 60,
+        created_at: 1_000,
+        status: public_commitment_fund::CommitmentStatus::Active,
+        title: ManagedBuffer::from("Read 12 books"),
+        proof_url: ManagedBuffer::new(),
+        proof_hash: ManagedBuffer::new(),
+        proof_submitted_at: 0,
+        finalized_at: 0,
+        fallback: address(1).into(),
+        claim_window_seconds: 600,
+        forgiven_amount: BigUint::from(1_000u64),
+        proof_kind: public_commitment_fund::ProofKind::HttpUrl,
+        oracle: ManagedAddress::zero(),
+        attester: address(3).into(),
+        start_at: 1_000,
+        cancel_grace_seconds: 100,
+        cancel_penalty_bps: 500,
+        cancellable: true,
+        tags: ManagedVec::from_single_item(ManagedBuffer::from("reading")),
+        description: ManagedBuffer::new(),
+        metadata_uri: ManagedBuffer::from("ipfs://bafy"),
+        metadata_hash: ManagedBuffer::new_from_bytes(&[9u8; 32]),
+    };
+    let data = top_encode_to_vec_u8_or_panic(&CreatedEventData {
+        version: EVENT_VERSION,
+        commitment,
+    });
+    let created = decode_event(&[b"CommitmentCreated".to_vec()], &data).unwrap().unwrap();
+    assert_eq!(created.id, 7);
+    let Change::Created(decoded) = created.change else {
+        panic!("expected a created commitment");
+    };
+    assert_eq!(decoded.title, "Read 12 books");
+    assert_eq!(decoded.creator, Bech32Address::from(address(1)));
+    assert_eq!(decoded.oracle, None);
+    assert_eq!(decoded.attester, Some(Bech32Address::from(address(3))));
+    assert_eq!(decoded.remaining_amount().to_string(), "4000");
+    assert_eq!(decoded.tags, vec!["reading".to_owned()]);
+    assert_eq!(decoded.proof_kind, ProofKind::HttpUrl);
+
+    let event_data = |version: u8| {
+        top_encode_to_vec_u8_or_panic(&EventData::<StaticApi> {
+            version,
+            caller: address(1).into(),
+            timestamp: 1_500,
+            status: public_commitment_fund::CommitmentStatus::Refunded,
+        })
+    };
+    let topics = vec![
+        b"Cancelled".to_vec(),
+        top_encode_to_vec_u8_or_panic(&7u64),
+        900u64.to_be_bytes().to_vec(),
+        100u64.to_be_bytes().to_vec(),
+    ];
+    let cancelled = decode_event(&topics, &event_data(EVENT_VERSION)).unwrap().unwrap();
+    assert_eq!(cancelled.id, 7);
+    assert_eq!(cancelled.meta.timestamp, 1_500);
+    assert_eq!(cancelled.meta.status, CommitmentStatus::Refunded);
+    assert_eq!(
+        cancelled.change,
+        Change::Cancelled {
+            refund: 900u32.into(),
+            penalty: 100u32.into(),
+        }
+    );
+
+    let topics = vec![
+        b"FailedFinalized".to_vec(),
+        top_encode_to_vec_u8_or_panic(&7u64),
+        top_encode_to_vec_u8_or_panic(&1_200u64),
+    ];
+    let failed = decode_event(&topics, &event_data(EVENT_VERSION)).unwrap().unwrap();
+    assert_eq!(failed.change, Change::FailedFinalized { finalized_at: 1_200 });
+
+    let err = decode_event(&topics, &event_data(EVENT_VERSION + 1)).unwrap_err();
+    assert!(err.to_string().contains("unsupported event version"));
+    assert_eq!(decode_event(&[b"Transfer".to_vec()], &[]).unwrap(), None);
+
+    let topics = vec![
+        b"DelegateAdded".to_vec(),
+        address(1).to_vec(),
+        top_encode_to_vec_u8_or_panic(&public_commitment_fund::DelegationKind::Claim),
+        address(4).to_vec(),
+    ];
+    let data = top_encode_to_vec_u8_or_panic(&public_commitment_fund::DelegationEventData {
+        version: EVENT_VERSION,
+        timestamp: 1_600,
+    });
+    assert_eq!(decode_event(&topics, &data).unwrap(), None);
+    let delegation = decode_delegation_event(&topics, &data).unwrap().unwrap();
+    assert_eq!(delegation.owner, Bech32Address::from(address(1)));
+    assert_eq!(delegation.kind, DelegationKind::Claim);
+    assert_eq!(delegation.delegate, Bech32Address::from(address(4)));
+    assert!(delegation.active);
+    assert_eq!(delegation.timestamp, 1_600);
+}
+
+#[test]
+fn parses_bech32_addresses() {
+    let alice = Bech32Address::from(address(1));
+    assert_eq!(parse_address(alice.to_bech32_str()).unwrap(), alice);
+    assert!(parse_address("erd1notanaddress").is_err());
+    let short = bech32::encode::<bech32::Bech32>(bech32::Hrp::parse("erd").unwrap(), &[1u8; 20])
+        .unwrap();
+    assert!(parse_address(&short).unwrap_err().contains("not 32 bytes"));
+}
@@ -0,0 +1,67 @@
+pub mod projection;
+pub mod source;
+pub mod store;
+
+use multiversx_sc_snippets::log;
+use source::EventSource;
+use store::Store;
+
+/// Pulls blocks from an [`EventSource`] into a [`Store`], keeping only the
+/// events emitted by `contract`.
+pub struct Indexer<S: EventSource> {
+    pub store: Store,
+    source: S,
+    contract: String,
+    start_nonce: u64,
+}
+
+impl<S: EventSource> Indexer<S> {
+    /// `start_nonce` is the first block read into an empty store; use the
+    /// contract's deploy block to skip the history before it.
+    pub fn new(store: Store, source: S, contract: &str, start_nonce: u64) -> Self {
+        Indexer {
+            store,
+            source,
+            contract: contract.to_owned(),
+            start_nonce,
+        }
+    }
+
+    /// Applies every block the source has past the checkpoint and returns how
+    /// many were applied. A block that does not extend the checkpoint means the
+    /// chain reorganized: the checkpoint block is rolled back and read again,
+    /// until the new branch links up.
+    pub async fn sync(&mut self) -> anyhow::Result<u64> {
+        let mut applied = 0;
+        loop {
+            let checkpoint = self.store.checkpoint()?;
+            let nonce = match &checkpoint {
+                Some(checkpoint) => checkpoint.nonce + 1,
+                None => self.start_nonce,
+            };
+            let Some(block) = self.source.block(nonce).await? else {
+                return Ok(applied);
+            };
+
+            if let Some(checkpoint) = checkpoint {
+                if block.prev_hash != checkpoint.hash {
+                    log::warn!("reorg at block {}, rolling back {}", nonce, checkpoint.nonce);
+                    match checkpoint.nonce.checked_sub(1) {
+                        Some(previous) => self.store.rollback_to(previous)?,
+                        None => self.store.clear()?,
+                    }
+                    continue;
+                }
+            }
+
+            let events: Vec<_> = block
+                .events
+                .iter()
+                .filter(|event| event.address == self.contract)
+                .cloned()
+                .collect();
+            self.store.apply_block(&block, &events)?;
+            applied += 1;
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use clap::Parser;
+use multiversx_sc_snippets::{env_logger, imports::tokio, log};
+use public_commitment_fund_indexer::{
+    source::{EventSource, FileSource, GatewaySource},
+    store::Store,
+    Indexer,
+};
+use std::{path::PathBuf, time::Duration};
+
+#[derive(Parser)]
+#[command(about = "Indexes the public commitment fund contract's events into SQLite")]
+struct Cli {
+    /// Bech32 address of the contract to index.
+    #[arg(long)]
+    contract: String,
+    /// SQLite database to write; created if missing.
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/commitments.sqlite"))]
+    db: PathBuf,
+    /// Gateway to read hyperblocks from.
+    #[arg(long, default_value = "https://devnet-gateway.multiversx.com")]
+    gateway: String,
+    /// Reads blocks from a JSON-lines event log instead of the gateway.
+    #[arg(long, conflicts_with = "gateway")]
+    events_file: Option<PathBuf>,
+    /// First block to read into an empty database, e.g. the deploy block.
+    #[arg(long, default_value_t = 0)]
+    start_nonce: u64,
+    /// Blocks to stay behind the chain tip.
+    #[arg(long, default_value_t = 3)]
+    confirmations: u64,
+    /// Seconds to wait between polls once caught up.
+    #[arg(long, default_value_t = 6)]
+    poll_seconds: u64,
+    /// Exits once caught up instead of polling.
+    #[arg(long)]
+    once: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::init();
+
+    let cli = Cli::parse();
+    let store = Store::open(&cli.db)?;
+    match &cli.events_file {
+        Some(path) => {
+            let source = FileSource::open(path)?;
+            run(&cli, Indexer::new(store, source, &cli.contract, cli.start_nonce)).await
+        },
+        None => {
+            let source = GatewaySource::new(&cli.gateway, cli.confirmations);
+            run(&cli, Indexer::new(store, source, &cli.contract, cli.start_nonce)).await
+        },
+    }
+}
+
+async fn run<S: EventSource>(cli: &Cli, mut indexer: Indexer<S>) -> anyhow::Result<()> {
+    loop {
+        let applied = indexer.sync().await?;
+        if let Some(checkpoint) = indexer.store.checkpoint()? {
+            log::info!("applied {applied} blocks, checkpoint at {}", checkpoint.nonce);
+        }
+        if cli.once {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(cli.poll_seconds)).await;
+    }
+}
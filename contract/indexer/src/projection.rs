@@ -0,0 +1,121 @@
+use multiversx_sc_snippets::imports::num_bigint::BigUint;
+use public_commitment_fund_client::{events::Change, ContractEvent, ProofKind};
+
+/// One row of the `commitments` table: the commitment as the contract holds it
+/// after every event indexed so far.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CommitmentRecord {
+    pub id: u64,
+    pub creator: String,
+    pub recipient: String,
+    pub amount: BigUint,
+    pub forgiven_amount: BigUint,
+    pub deadline: u64,
+    pub cooldown_seconds: u64,
+    pub claim_window_seconds: u64,
+    pub created_at: u64,
+    pub start_at: u64,
+    pub fallback: String,
+    pub oracle: Option<String>,
+    pub attester: Option<String>,
+    pub cancel_grace_seconds: u64,
+    pub cancel_penalty_bps: u64,
+    pub cancellable: bool,
+    /// `CommitmentStatus` variant name, e.g. `Active`.
+    pub status: String,
+    pub title: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub metadata_uri: String,
+    /// Hex-encoded; empty when none.
+    pub metadata_hash: String,
+    /// `ProofKind` variant name; empty until a proof is recorded.
+    pub proof_kind: String,
+    pub proof_url: String,
+    /// Hex-encoded; empty when none.
+    pub proof_hash: String,
+    pub proof_submitted_at: u64,
+    pub finalized_at: u64,
+    /// Timestamp of the last event applied.
+    pub updated_at: u64,
+}
+
+impl CommitmentRecord {
+    /// Folds `event` into the record. `current` is `None` for ids not seen yet,
+    /// so only `CommitmentCreated` can start a record; anything else for an
+    /// unknown id (e.g. when indexing starts mid-history) is ignored.
+    pub fn apply(current: Option<Self>, event: &ContractEvent) -> Option<Self> {
+        let mut record = match (&event.change, current) {
+            (Change::Created(created), _) => CommitmentRecord {
+                id: event.id,
+                creator: created.creator.to_bech32_string(),
+                recipient: created.recipient.to_bech32_string(),
+                amount: created.amount.clone(),
+                deadline: created.deadline,
+                cooldown_seconds: created.cooldown_seconds,
+                claim_window_seconds: created.claim_window_seconds,
+                created_at: event.meta.timestamp,
+                start_at: created.start_at,
+                fallback: created.fallback.to_bech32_string(),
+                oracle: created.oracle.as_ref().map(|oracle| oracle.to_bech32_string()),
+                attester: created.attester.as_ref().map(|attester| attester.to_bech32_string()),
+                cancel_grace_seconds: created.cancel_grace_seconds,
+                cancel_penalty_bps: created.cancel_penalty_bps,
+                cancellable: created.cancellable,
+                title: created.title.clone(),
+                description: created.description.clone(),
+                tags: created.tags.clone(),
+                metadata_uri: created.metadata_uri.clone(),
+                metadata_hash: hex::encode(&created.metadata_hash),
+                ..CommitmentRecord::default()
+            },
+            (_, Some(record)) => record,
+            (_, None) => return None,
+        };
+
+        let now = event.meta.timestamp;
+        record.status = format!("{:?}", event.meta.status);
+        record.updated_at = now;
+
+        match &event.change {
+            Change::ProofSubmitted { kind, reference, proof_hash }
+            | Change::ProofRevised { kind, reference, proof_hash, .. } => {
+                record.proof_kind = format!("{kind:?}");
+                record.proof_url = String::from_utf8_lossy(reference).into_owned();
+                record.proof_hash = hex::encode(proof_hash);
+                record.proof_submitted_at = now;
+            },
+            Change::ProofSealed { proof_hash } => {
+                record.proof_hash = hex::encode(proof_hash);
+                record.proof_submitted_at = now;
+            },
+            Change::ProofRevealed { proof_url, proof_hash } => {
+                record.proof_url = String::from_utf8_lossy(proof_url).into_owned();
+                record.proof_hash = hex::encode(proof_hash);
+            },
+            Change::OracleVerified { verified: true } => {
+                record.proof_kind = format!("{:?}", ProofKind::OracleAttestation);
+                record.proof_url = record.oracle.clone().unwrap_or_default();
+                record.proof_hash = String::new();
+                record.proof_submitted_at = now;
+            },
+            Change::FailedFinalized { finalized_at } => record.finalized_at = *finalized_at,
+            Change::Conceded
+            | Change::Refunded { .. }
+            | Change::Cancelled { .. } => {
+                record.finalized_at = now;
+            },
+            Change::Forgiven { amount, .. } => record.forgiven_amount += amount,
+            Change::Created(_)
+            | Change::OracleVerificationRequested { .. }
+            | Change::OracleVerified { verified: false }
+            | Change::OracleVerificationFailed
+            | Change::Claimed { .. }
+            | Change::ReleaseRequested { .. }
+            | Change::ReleaseFailed { .. }
+            | Change::Swept { .. }
+            | Change::Pruned { .. } => {},
+        }
+        Some(record)
+    }
+}
@@ -0,0 +1,216 @@
+use anyhow::{anyhow, Context};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use multiversx_sc_snippets::{
+    imports::GatewayHttpProxy,
+    sdk::{
+        data::transaction::{ApiLogs, Events, LogData},
+        gateway::{GatewayRequest, GatewayRequestType, NetworkStatusRequest},
+    },
+};
+use public_commitment_fund_client::{decode_event, ContractEvent};
+use serde::Deserialize;
+use std::{collections::BTreeMap, path::Path};
+
+/// One log entry as read from a block, before it is matched to a contract event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawEvent {
+    pub tx_hash: String,
+    /// Bech32 address of the emitting contract.
+    pub address: String,
+    /// `topics[0]` is the event identifier, e.g. `CommitmentCreated`.
+    pub topics: Vec<Vec<u8>>,
+    pub data: Vec<u8>,
+}
+
+impl RawEvent {
+    fn from_api(tx_hash: &str, event: &Events) -> anyhow::Result<Self> {
+        let topics = event
+            .topics
+            .iter()
+            .map(|topic| BASE64.decode(topic))
+            .collect::<Result<_, _>>()
+            .with_context(|| format!("invalid topic in tx {tx_hash}"))?;
+
+        // Contract events carry a single data field; the gateway may still wrap
+        // it in a list.
+        let data = match &event.data {
+            LogData::Empty => Vec::new(),
+            LogData::String(data) => BASE64.decode(data)?,
+            LogData::Vec(data) => match data.first() {
+                Some(data) => BASE64.decode(data)?,
+                None => Vec::new(),
+            },
+        };
+
+        Ok(RawEvent {
+            tx_hash: tx_hash.to_owned(),
+            address: event.address.to_bech32_string(),
+            topics,
+            data,
+        })
+    }
+
+    /// Decodes the event against the client's schema; `None` for events the
+    /// contract does not emit.
+    pub fn decode(&self) -> anyhow::Result<Option<ContractEvent>> {
+        decode_event(&self.topics, &self.data).with_context(|| format!("in tx {}", self.tx_hash))
+    }
+}
+
+/// A metachain hyperblock, reduced to what the indexer needs to detect reorgs
+/// and replay events in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub nonce: u64,
+    pub hash: String,
+    pub prev_hash: String,
+    pub events: Vec<RawEvent>,
+}
+
+#[allow(async_fn_in_trait)]
+pub trait EventSource {
+    /// The block at `nonce`, or `None` while it is not available yet.
+    async fn block(&mut self, nonce: u64) -> anyhow::Result<Option<Block>>;
+}
+
+/// A transaction and its logs, as listed in a hyperblock or an event-log file.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoggedTx {
+    hash: String,
+    #[serde(default)]
+    logs: Option<ApiLogs>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HyperBlock {
+    nonce: u64,
+    hash: String,
+    prev_block_hash: String,
+    #[serde(default)]
+    transactions: Vec<LoggedTx>,
+}
+
+impl HyperBlock {
+    fn into_block(self) -> anyhow::Result<Block> {
+        let mut events = Vec::new();
+        for tx in &self.transactions {
+            for event in tx.logs.iter().flat_map(|logs| &logs.events) {
+                events.push(RawEvent::from_api(&tx.hash, event)?);
+            }
+        }
+
+        Ok(Block {
+            nonce: self.nonce,
+            hash: self.hash,
+            prev_hash: self.prev_block_hash,
+            events,
+        })
+    }
+}
+
+/// Reads blocks from a JSON-lines file, one hyperblock per line, in the same
+/// shape the gateway returns them:
+/// `{"nonce":1,"hash":"..","prevBlockHash":"..","transactions":[{"hash":"..","logs":{..}}]}`.
+/// Nonces must be contiguous; the file is read once, when opened.
+pub struct FileSource {
+    blocks: BTreeMap<u64, Block>,
+}
+
+impl FileSource {
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("cannot read {}", path.display()))?;
+
+        let mut blocks = BTreeMap::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let block = serde_json::from_str::<HyperBlock>(line)
+                .map_err(anyhow::Error::from)
+                .and_then(HyperBlock::into_block)
+                .with_context(|| format!("{}:{}", path.display(), index + 1))?;
+            blocks.insert(block.nonce, block);
+        }
+        Ok(FileSource { blocks })
+    }
+}
+
+impl EventSource for FileSource {
+    async fn block(&mut self, nonce: u64) -> anyhow::Result<Option<Block>> {
+        Ok(self.blocks.get(&nonce).cloned())
+    }
+}
+
+/// Reads hyperblocks from a gateway, staying `confirmations` blocks behind the
+/// metachain tip.
+pub struct GatewaySource {
+    proxy: GatewayHttpProxy,
+    confirmations: u64,
+    /// Highest nonce known to be deep enough; refreshed only when passed.
+    ready_nonce: u64,
+}
+
+impl GatewaySource {
+    pub fn new(gateway_uri: &str, confirmations: u64) -> Self {
+        GatewaySource {
+            proxy: GatewayHttpProxy::new(gateway_uri.trim_end_matches('/').to_owned()),
+            confirmations,
+            ready_nonce: 0,
+        }
+    }
+}
+
+impl EventSource for GatewaySource {
+    async fn block(&mut self, nonce: u64) -> anyhow::Result<Option<Block>> {
+        if nonce > self.ready_nonce {
+            let status = self.proxy.http_request(NetworkStatusRequest::default()).await?;
+            self.ready_nonce = status.nonce.saturating_sub(self.confirmations);
+            if nonce > self.ready_nonce {
+                return Ok(None);
+            }
+        }
+
+        let hyperblock = self.proxy.http_request(HyperBlockWithLogsRequest { nonce }).await?;
+        hyperblock.into_block().map(Some)
+    }
+}
+
+/// `hyperblock/by-nonce`, with the transaction logs the SDK request leaves out.
+struct HyperBlockWithLogsRequest {
+    nonce: u64,
+}
+
+#[derive(Deserialize)]
+struct HyperBlockResponse {
+    data: Option<HyperBlockData>,
+    error: String,
+}
+
+#[derive(Deserialize)]
+struct HyperBlockData {
+    hyperblock: HyperBlock,
+}
+
+impl GatewayRequest for HyperBlockWithLogsRequest {
+    type Payload = ();
+    type DecodedJson = HyperBlockResponse;
+    type Result = HyperBlock;
+
+    fn request_type(&self) -> GatewayRequestType {
+        GatewayRequestType::Get
+    }
+
+    fn get_endpoint(&self) -> String {
+        format!("hyperblock/by-nonce/{}?withLogs=true", self.nonce)
+    }
+
+    fn process_json(&self, decoded: Self::DecodedJson) -> anyhow::Result<Self::Result> {
+        match decoded.data {
+            None => Err(anyhow!("{}", decoded.error)),
+            Some(data) => Ok(data.hyperblock),
+        }
+    }
+}
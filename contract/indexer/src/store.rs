@@ -0,0 +1,354 @@
+use crate::{
+    projection::CommitmentRecord,
+    source::{Block, RawEvent},
+};
+use anyhow::Context;
+use multiversx_sc_snippets::log;
+use public_commitment_fund_client::ContractEvent;
+use rusqlite::{params, Connection, OptionalExtension, Row, Transaction};
+use std::path::Path;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS blocks (
+    nonce INTEGER PRIMARY KEY,
+    hash TEXT NOT NULL,
+    prev_hash TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS events (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    block_nonce INTEGER NOT NULL,
+    tx_hash TEXT NOT NULL,
+    commitment_id INTEGER NOT NULL,
+    name TEXT NOT NULL,
+    topics TEXT NOT NULL,
+    data BLOB NOT NULL
+);
+CREATE INDEX IF NOT EXISTS events_by_block ON events (block_nonce);
+CREATE INDEX IF NOT EXISTS events_by_commitment ON events (commitment_id, seq);
+CREATE TABLE IF NOT EXISTS undecoded_events (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    block_nonce INTEGER NOT NULL,
+    tx_hash TEXT NOT NULL,
+    topics TEXT NOT NULL,
+    data BLOB NOT NULL,
+    error TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS undecoded_events_by_block ON undecoded_events (block_nonce);
+CREATE TABLE IF NOT EXISTS commitments (
+    id INTEGER PRIMARY KEY,
+    creator TEXT NOT NULL,
+    recipient TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    forgiven_amount TEXT NOT NULL,
+    deadline INTEGER NOT NULL,
+    cooldown_seconds INTEGER NOT NULL,
+    claim_window_seconds INTEGER NOT NULL,
+    created_at INTEGER NOT NULL,
+    start_at INTEGER NOT NULL,
+    fallback TEXT NOT NULL,
+    oracle TEXT,
+    attester TEXT,
+    cancel_grace_seconds INTEGER NOT NULL,
+    cancel_penalty_bps INTEGER NOT NULL,
+    cancellable INTEGER NOT NULL,
+    status TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    tags TEXT NOT NULL,
+    metadata_uri TEXT NOT NULL,
+    metadata_hash TEXT NOT NULL,
+    proof_kind TEXT NOT NULL,
+    proof_url TEXT NOT NULL,
+    proof_hash TEXT NOT NULL,
+    proof_submitted_at INTEGER NOT NULL,
+    finalized_at INTEGER NOT NULL,
+    updated_at INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS commitments_by_status ON commitments (status, deadline);
+";
+
+const COMMITMENT_COLUMNS: &str = "id, creator, recipient, amount, forgiven_amount, deadline, \
+    cooldown_seconds, claim_window_seconds, created_at, start_at, fallback, oracle, attester, \
+    cancel_grace_seconds, cancel_penalty_bps, cancellable, status, title, description, tags, \
+    metadata_uri, metadata_hash, proof_kind, proof_url, proof_hash, proof_submitted_at, \
+    finalized_at, updated_at";
+
+/// Last indexed block; the next block must point back at `hash`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub nonce: u64,
+    pub hash: String,
+}
+
+/// SQLite database holding every indexed block and contract event, plus the
+/// `commitments` projection folded from them. Each block is applied in one
+/// transaction, so the checkpoint never runs ahead of the projection.
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("cannot open {}", path.display()))?;
+        Self::init(conn)
+    }
+
+    pub fn open_in_memory() -> anyhow::Result<Self> {
+        Self::init(Connection::open_in_memory()?)
+    }
+
+    fn init(conn: Connection) -> anyhow::Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+        Ok(Store { conn })
+    }
+
+    pub fn checkpoint(&self) -> anyhow::Result<Option<Checkpoint>> {
+        let checkpoint = self
+            .conn
+            .query_row("SELECT nonce, hash FROM blocks ORDER BY nonce DESC LIMIT 1", [], |row| {
+                Ok(Checkpoint {
+                    nonce: row.get(0)?,
+                    hash: row.get(1)?,
+                })
+            })
+            .optional()?;
+        Ok(checkpoint)
+    }
+
+    /// Records `block` and folds its events into the projection. `events` must
+    /// already be limited to the indexed contract. Events that fail to decode
+    /// are kept in `undecoded_events` instead of stopping the sync.
+    pub fn apply_block(&mut self, block: &Block, events: &[RawEvent]) -> anyhow::Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute(
+            "INSERT INTO blocks (nonce, hash, prev_hash) VALUES (?1, ?2, ?3)",
+            params![block.nonce, block.hash, block.prev_hash],
+        )?;
+
+        for raw in events {
+            let topics: Vec<String> = raw.topics.iter().map(hex::encode).collect();
+            let topics = serde_json::to_string(&topics)?;
+            let event = match raw.decode() {
+                Ok(Some(event)) => event,
+                Ok(None) => continue,
+                Err(err) => {
+                    log::warn!("setting aside an event of block {}: {err:#}", block.nonce);
+                    tx.execute(
+                        "INSERT INTO undecoded_events (block_nonce, tx_hash, topics, data, error)
+                         VALUES (?1, ?2, ?3, ?4, ?5)",
+                        params![block.nonce, raw.tx_hash, topics, raw.data, format!("{err:#}")],
+                    )?;
+                    continue;
+                },
+            };
+            tx.execute(
+                "INSERT INTO events (block_nonce, tx_hash, commitment_id, name, topics, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![block.nonce, raw.tx_hash, event.id, event.name, topics, raw.data],
+            )?;
+            project(&tx, &event)?;
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Forgets every block above `nonce` and their events, then rebuilds the
+    /// commitments they touched by replaying the events that remain.
+    pub fn rollback_to(&mut self, nonce: u64) -> anyhow::Result<()> {
+        let tx = self.conn.transaction()?;
+
+        let touched = {
+            let mut stmt =
+                tx.prepare("SELECT DISTINCT commitment_id FROM events WHERE block_nonce > ?1")?;
+            let ids = stmt.query_map([nonce], |row| row.get::<_, u64>(0))?;
+            ids.collect::<Result<Vec<_>, _>>()?
+        };
+        tx.execute("DELETE FROM events WHERE block_nonce > ?1", [nonce])?;
+        tx.execute("DELETE FROM undecoded_events WHERE block_nonce > ?1", [nonce])?;
+        tx.execute("DELETE FROM blocks WHERE nonce > ?1", [nonce])?;
+
+        for id in touched {
+            tx.execute("DELETE FROM commitments WHERE id = ?1", [id])?;
+            for event in stored_events(&tx, id)? {
+                project(&tx, &event)?;
+            }
+        }
+
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Forgets every block, event and commitment, for a reorg that reaches
+    /// the first indexed block.
+    pub fn clear(&mut self) -> anyhow::Result<()> {
+        self.conn.execute_batch(
+            "BEGIN;
+             DELETE FROM events;
+             DELETE FROM undecoded_events;
+             DELETE FROM blocks;
+             DELETE FROM commitments;
+             COMMIT;",
+        )?;
+        Ok(())
+    }
+
+    pub fn commitment(&self, id: u64) -> anyhow::Result<Option<CommitmentRecord>> {
+        load_commitment(&self.conn, id)
+    }
+
+    pub fn commitments(&self) -> anyhow::Result<Vec<CommitmentRecord>> {
+        let mut stmt = self
+            .conn
+            .prepare(&format!("SELECT {COMMITMENT_COLUMNS} FROM commitments ORDER BY id"))?;
+        let records = stmt.query_map([], read_commitment)?;
+        Ok(records.collect::<Result<_, _>>()?)
+    }
+
+    /// Names of the events indexed for `id`, oldest first.
+    pub fn event_names(&self, id: u64) -> anyhow::Result<Vec<String>> {
+        let mut stmt =
+            self.conn.prepare("SELECT name FROM events WHERE commitment_id = ?1 ORDER BY seq")?;
+        let names = stmt.query_map([id], |row| row.get(0))?;
+        Ok(names.collect::<Result<_, _>>()?)
+    }
+
+    /// Block nonce and decode error of every event set aside, oldest first.
+    pub fn undecoded_events(&self) -> anyhow::Result<Vec<(u64, String)>> {
+        let mut stmt =
+            self.conn.prepare("SELECT block_nonce, error FROM undecoded_events ORDER BY seq")?;
+        let events = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(events.collect::<Result<_, _>>()?)
+    }
+}
+
+fn project(tx: &Transaction, event: &ContractEvent) -> anyhow::Result<()> {
+    let current = load_commitment(tx, event.id)?;
+    let Some(record) = CommitmentRecord::apply(current, event) else {
+        log::warn!("skipping {} for unknown commitment {}", event.name, event.id);
+        return Ok(());
+    };
+
+    tx.execute(
+        &format!(
+            "INSERT OR REPLACE INTO commitments ({COMMITMENT_COLUMNS})
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                     ?18, ?19, ?20, ?21, ?22, ?23, ?24, ?25, ?26, ?27, ?28)"
+        ),
+        params![
+            record.id,
+            record.creator,
+            record.recipient,
+            record.amount.to_string(),
+            record.forgiven_amount.to_string(),
+            record.deadline,
+            record.cooldown_seconds,
+            record.claim_window_seconds,
+            record.created_at,
+            record.start_at,
+            record.fallback,
+            record.oracle,
+            record.attester,
+            record.cancel_grace_seconds,
+            record.cancel_penalty_bps,
+            record.cancellable,
+            record.status,
+            record.title,
+            record.description,
+            record.tags.join(","),
+            record.metadata_uri,
+            record.metadata_hash,
+            record.proof_kind,
+            record.proof_url,
+            record.proof_hash,
+            record.proof_submitted_at,
+            record.finalized_at,
+            record.updated_at,
+        ],
+    )?;
+    Ok(())
+}
+
+fn stored_events(conn: &Connection, id: u64) -> anyhow::Result<Vec<ContractEvent>> {
+    let mut stmt = conn.prepare(
+        "SELECT tx_hash, topics, data FROM events WHERE commitment_id = ?1 ORDER BY seq",
+    )?;
+    let rows = stmt.query_map([id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Vec<u8>>(2)?))
+    })?;
+
+    let mut events = Vec::new();
+    for row in rows {
+        let (tx_hash, topics, data) = row?;
+        let topics = serde_json::from_str::<Vec<String>>(&topics)?
+            .iter()
+            .map(hex::decode)
+            .collect::<Result<_, _>>()?;
+        let raw = RawEvent {
+            tx_hash,
+            address: String::new(),
+            topics,
+            data,
+        };
+        // Stored events decoded once already. Should a newer client reject
+        // one, the replay skips it rather than leaving the reorg half done.
+        match raw.decode() {
+            Ok(event) => events.extend(event),
+            Err(err) => log::warn!("skipping a stored event of commitment {id}: {err:#}"),
+        }
+    }
+    Ok(events)
+}
+
+fn load_commitment(conn: &Connection, id: u64) -> anyhow::Result<Option<CommitmentRecord>> {
+    let record = conn
+        .query_row(
+            &format!("SELECT {COMMITMENT_COLUMNS} FROM commitments WHERE id = ?1"),
+            [id],
+            read_commitment,
+        )
+        .optional()?;
+    Ok(record)
+}
+
+fn read_commitment(row: &Row) -> rusqlite::Result<CommitmentRecord> {
+    let big_uint = |index: usize| -> rusqlite::Result<_> {
+        row.get::<_, String>(index)?.parse().map_err(|err| {
+            let kind = rusqlite::types::Type::Text;
+            rusqlite::Error::FromSqlConversionFailure(index, kind, Box::new(err))
+        })
+    };
+    let tags: String = row.get(19)?;
+
+    Ok(CommitmentRecord {
+        id: row.get(0)?,
+        creator: row.get(1)?,
+        recipient: row.get(2)?,
+        amount: big_uint(3)?,
+        forgiven_amount: big_uint(4)?,
+        deadline: row.get(5)?,
+        cooldown_seconds: row.get(6)?,
+        claim_window_seconds: row.get(7)?,
+        created_at: row.get(8)?,
+        start_at: row.get(9)?,
+        fallback: row.get(10)?,
+        oracle: row.get(11)?,
+        attester: row.get(12)?,
+        cancel_grace_seconds: row.get(13)?,
+        cancel_penalty_bps: row.get(14)?,
+        cancellable: row.get(15)?,
+        status: row.get(16)?,
+        title: row.get(17)?,
+        description: row.get(18)?,
+        tags: tags.split(',').filter(|tag| !tag.is_empty()).map(str::to_owned).collect(),
+        metadata_uri: row.get(20)?,
+        metadata_hash: row.get(21)?,
+        proof_kind: row.get(22)?,
+        proof_url: row.get(23)?,
+        proof_hash: row.get(24)?,
+        proof_submitted_at: row.get(25)?,
+        finalized_at: row.get(26)?,
+        updated_at: row.get(27)?,
+    })
+}
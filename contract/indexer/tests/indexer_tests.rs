@@ -0,0 +1,341 @@
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use multiversx_sc::codec::{top_encode_to_vec_u8_or_panic, TopEncode};
+use multiversx_sc_snippets::imports::*;
+use public_commitment_fund_client::{
+    proxy::{Commitment, CreatedEventData, EventData},
+    CommitmentStatus, ProofKind, EVENT_VERSION,
+};
+use public_commitment_fund_indexer::{source::FileSource, store::Store, Indexer};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+const CREATED_AT: u64 = 1_000;
+const DEADLINE: u64 = 5_000;
+
+fn address(byte: u8) -> Address {
+    Address::from([byte; 32])
+}
+
+fn bech32(address: &Address) -> String {
+    Bech32Address::from(address).to_bech32_string()
+}
+
+fn contract() -> String {
+    bech32(&address(0xcc))
+}
+
+fn topic<T: TopEncode>(value: &T) -> String {
+    BASE64.encode(top_encode_to_vec_u8_or_panic(value))
+}
+
+fn event(address: &str, topics: Vec<String>, data: Vec<u8>) -> Value {
+    json!({
+        "address": address,
+        "identifier": "callEndpoint",
+        "topics": topics,
+        "data": BASE64.encode(data),
+    })
+}
+
+fn created(id: u64) -> Value {
+    let commitment = Commitment::<StaticApi> {
+        id,
+        creator: address(1).into(),
+        recipient: address(2).into(),
+        amount: BigUint::from(1_000_000u64),
+        deadline: DEADLINE,
+        cooldown_seconds: 86_400,
+        created_at: CREATED_AT,
+        status: CommitmentStatus::Active,
+        title: ManagedBuffer::from("Run 5k"),
+        proof_url: ManagedBuffer::new(),
+        proof_hash: ManagedBuffer::new(),
+        proof_submitted_at: 0,
+        finalized_at: 0,
+        fallback: address(1).into(),
+        claim_window_seconds: 365 * 86_400,
+        forgiven_amount: BigUint::zero(),
+        proof_kind: ProofKind::HttpUrl,
+        oracle: ManagedAddress::zero(),
+        attester: address(3).into(),
+        start_at: CREATED_AT,
+        cancel_grace_seconds: 86_400,
+        cancel_penalty_bps: 1_000,
+        cancellable: true,
+        tags: ManagedVec::from_single_item(ManagedBuffer::from("fitness")),
+        description: ManagedBuffer::new(),
+        metadata_uri: ManagedBuffer::new(),
+        metadata_hash: ManagedBuffer::new(),
+    };
+    let topics = vec![
+        topic(&"CommitmentCreated"),
+        topic(&id),
+        topic(&commitment.creator),
+        topic(&commitment.recipient),
+        topic(&commitment.amount),
+        topic(&DEADLINE),
+        topic(&commitment.cooldown_seconds),
+    ];
+    let data = CreatedEventData {
+        version: EVENT_VERSION,
+        commitment,
+    };
+    event(&contract(), topics, top_encode_to_vec_u8_or_panic(&data))
+}
+
+fn transition(
+    name: &str,
+    id: u64,
+    mut topics: Vec<String>,
+    status: CommitmentStatus,
+    timestamp: u64,
+) -> Value {
+    topics.insert(0, topic(&name));
+    topics.insert(1, topic(&id));
+    let data = EventData::<StaticApi> {
+        version: EVENT_VERSION,
+        caller: address(1).into(),
+        timestamp,
+        status,
+    };
+    event(&contract(), topics, top_encode_to_vec_u8_or_panic(&data))
+}
+
+/// Address and amount topics of `Refunded`, `Claimed` and the like.
+fn payout(to: u8) -> Vec<String> {
+    vec![BASE64.encode(address(to).as_bytes()), topic(&BigUint::<StaticApi>::from(1_000_000u64))]
+}
+
+fn proof_submitted(id: u64, url: &str, timestamp: u64) -> Value {
+    let topics = vec![topic(&ProofKind::HttpUrl), topic(&url), topic(&[0xabu8; 32].as_slice())];
+    transition("ProofSubmitted", id, topics, CommitmentStatus::Completed, timestamp)
+}
+
+fn block(nonce: u64, hash: &str, prev_hash: &str, events: Vec<Value>) -> Value {
+    json!({
+        "nonce": nonce,
+        "hash": hash,
+        "prevBlockHash": prev_hash,
+        "transactions": [{
+            "hash": format!("tx-{hash}"),
+            "logs": { "address": contract(), "events": events },
+        }],
+    })
+}
+
+fn write_log(name: &str, blocks: &[Value]) -> PathBuf {
+    let path = std::env::temp_dir()
+        .join(format!("pcf-indexer-{}-{name}.jsonl", std::process::id()));
+    let lines: Vec<String> = blocks.iter().map(Value::to_string).collect();
+    std::fs::write(&path, lines.join("\n")).unwrap();
+    path
+}
+
+async fn index(store: Store, path: &Path) -> (Indexer<FileSource>, u64) {
+    let mut indexer = Indexer::new(store, FileSource::open(path).unwrap(), &contract(), 1);
+    let applied = indexer.sync().await.unwrap();
+    (indexer, applied)
+}
+
+#[tokio::test]
+async fn projects_commitments_from_event_log() {
+    let other_contract = bech32(&address(0xdd));
+    let blocks = [
+        block(1, "h1", "h0", vec![created(1), created(2)]),
+        block(
+            2,
+            "h2",
+            "h1",
+            vec![
+                proof_submitted(1, "https://example.com/proof", 2_000),
+                event(&other_contract, vec![topic(&"CommitmentCreated")], Vec::new()),
+                event(&contract(), vec![topic(&"SomethingElse")], Vec::new()),
+            ],
+        ),
+        block(
+            3,
+            "h3",
+            "h2",
+            vec![
+                transition("Refunded", 1, payout(1), CommitmentStatus::Refunded, 6_000),
+                // Released after it lapsed, so dated to its deadline.
+                transition(
+                    "FailedFinalized",
+                    2,
+                    vec![topic(&DEADLINE)],
+                    CommitmentStatus::Failed,
+                    6_000,
+                ),
+                transition("Claimed", 2, payout(2), CommitmentStatus::Claimed, 99_000),
+            ],
+        ),
+    ];
+    let path = write_log("lifecycle", &blocks);
+
+    let (mut indexer, applied) = index(Store::open_in_memory().unwrap(), &path).await;
+    assert_eq!(applied, 3);
+    assert_eq!(indexer.store.checkpoint().unwrap().unwrap().nonce, 3);
+
+    let first = indexer.store.commitment(1).unwrap().unwrap();
+    assert_eq!(first.status, "Refunded");
+    assert_eq!(first.title, "Run 5k");
+    assert_eq!(first.creator, bech32(&address(1)));
+    assert_eq!(first.amount.to_string(), "1000000");
+    assert_eq!(first.tags, vec!["fitness".to_owned()]);
+    assert_eq!(first.oracle, None);
+    assert_eq!(first.attester, Some(bech32(&address(3))));
+    assert_eq!(first.cancel_grace_seconds, 86_400);
+    assert_eq!(first.cancel_penalty_bps, 1_000);
+    assert!(first.cancellable);
+    assert_eq!(first.proof_kind, "HttpUrl");
+    assert_eq!(first.proof_url, "https://example.com/proof");
+    assert_eq!(first.proof_hash, hex::encode([0xab; 32]));
+    assert_eq!(first.proof_submitted_at, 2_000);
+    assert_eq!(first.finalized_at, 6_000);
+
+    let second = indexer.store.commitment(2).unwrap().unwrap();
+    assert_eq!(second.status, "Claimed");
+    assert_eq!(second.finalized_at, DEADLINE);
+    assert_eq!(second.updated_at, 99_000);
+    assert_eq!(
+        indexer.store.event_names(2).unwrap(),
+        vec!["CommitmentCreated", "FailedFinalized", "Claimed"]
+    );
+    assert_eq!(indexer.store.commitments().unwrap().len(), 2);
+
+    // Already caught up: nothing is applied twice.
+    assert_eq!(indexer.sync().await.unwrap(), 0);
+    assert_eq!(indexer.store.event_names(1).unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn reorg_rolls_back_orphaned_blocks_and_replays() {
+    let db = std::env::temp_dir().join(format!("pcf-indexer-{}-reorg.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&db);
+
+    let original = write_log(
+        "original",
+        &[
+            block(1, "h1", "h0", vec![created(1)]),
+            block(2, "h2", "h1", vec![]),
+            block(3, "h3", "h2", vec![proof_submitted(1, "https://example.com/orphan", 2_000)]),
+        ],
+    );
+    let (indexer, _) = index(Store::open(&db).unwrap(), &original).await;
+    assert_eq!(indexer.store.commitment(1).unwrap().unwrap().status, "Completed");
+    drop(indexer);
+
+    // Blocks 2 and 3 were replaced by another branch; block 4 extends it.
+    let forked = write_log(
+        "forked",
+        &[
+            block(1, "h1", "h0", vec![created(1)]),
+            block(2, "h2b", "h1", vec![]),
+            block(3, "h3b", "h2b", vec![]),
+            block(
+                4,
+                "h4b",
+                "h3b",
+                vec![transition(
+                    "FailedFinalized",
+                    1,
+                    vec![topic(&6_000u64)],
+                    CommitmentStatus::Failed,
+                    6_000,
+                )],
+            ),
+        ],
+    );
+    let (indexer, applied) = index(Store::open(&db).unwrap(), &forked).await;
+    assert_eq!(applied, 3);
+    assert_eq!(indexer.store.checkpoint().unwrap().unwrap().hash, "h4b");
+
+    let commitment = indexer.store.commitment(1).unwrap().unwrap();
+    assert_eq!(commitment.status, "Failed");
+    assert_eq!(commitment.proof_url, "");
+    assert_eq!(commitment.proof_submitted_at, 0);
+    assert_eq!(commitment.finalized_at, 6_000);
+    assert_eq!(
+        indexer.store.event_names(1).unwrap(),
+        vec!["CommitmentCreated", "FailedFinalized"]
+    );
+}
+
+#[tokio::test]
+async fn sets_aside_events_from_another_schema_version() {
+    let mut stale = proof_submitted(1, "https://example.com/proof", 2_000);
+    let data = EventData::<StaticApi> {
+        version: EVENT_VERSION + 1,
+        caller: address(1).into(),
+        timestamp: 2_000,
+        status: CommitmentStatus::Completed,
+    };
+    stale["data"] = json!(BASE64.encode(top_encode_to_vec_u8_or_panic(&data)));
+    let path = write_log(
+        "version",
+        &[
+            block(1, "h1", "h0", vec![created(1)]),
+            block(2, "h2", "h1", vec![stale]),
+            block(3, "h3", "h2", vec![proof_submitted(1, "https://example.com/later", 3_000)]),
+        ],
+    );
+
+    // The stale event is kept aside, not misread, and later blocks still apply.
+    let (indexer, applied) = index(Store::open_in_memory().unwrap(), &path).await;
+    assert_eq!(applied, 3);
+    let undecoded = indexer.store.undecoded_events().unwrap();
+    assert_eq!(undecoded.len(), 1);
+    assert_eq!(undecoded[0].0, 2);
+    assert!(undecoded[0].1.contains("unsupported event version"));
+
+    let commitment = indexer.store.commitment(1).unwrap().unwrap();
+    assert_eq!(commitment.proof_url, "https://example.com/later");
+    assert_eq!(
+        indexer.store.event_names(1).unwrap(),
+        vec!["CommitmentCreated", "ProofSubmitted"]
+    );
+}
+
+#[tokio::test]
+async fn reorg_reaching_genesis_clears_the_store() {
+    let db = std::env::temp_dir()
+        .join(format!("pcf-indexer-{}-genesis.sqlite", std::process::id()));
+    let _ = std::fs::remove_file(&db);
+
+    let original = write_log(
+        "genesis-original",
+        &[
+            block(0, "h0", "", vec![created(1)]),
+            block(1, "h1", "h0", vec![proof_submitted(1, "https://example.com/orphan", 2_000)]),
+        ],
+    );
+    let mut indexer = Indexer::new(
+        Store::open(&db).unwrap(),
+        FileSource::open(&original).unwrap(),
+        &contract(),
+        0,
+    );
+    assert_eq!(indexer.sync().await.unwrap(), 2);
+    drop(indexer);
+
+    // Every block, genesis included, was replaced by another branch.
+    let forked = write_log(
+        "genesis-forked",
+        &[
+            block(0, "h0b", "", vec![created(2)]),
+            block(1, "h1b", "h0b", vec![]),
+            block(2, "h2b", "h1b", vec![]),
+        ],
+    );
+    let mut indexer = Indexer::new(
+        Store::open(&db).unwrap(),
+        FileSource::open(&forked).unwrap(),
+        &contract(),
+        0,
+    );
+    assert_eq!(indexer.sync().await.unwrap(), 3);
+    assert_eq!(indexer.store.checkpoint().unwrap().unwrap().hash, "h2b");
+    assert_eq!(indexer.store.commitment(1).unwrap(), None);
+    assert_eq!(indexer.store.commitment(2).unwrap().unwrap().status, "Active");
+    assert_eq!(indexer.store.event_names(1).unwrap(), Vec::<String>::new());
+}
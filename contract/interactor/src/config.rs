@@ -0,0 +1,48 @@
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+const CHAIN_SIMULATOR_GATEWAY: &str = "http://localhost:8085";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChainType {
+    Real,
+    Simulator,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Config {
+    pub gateway_uri: String,
+    pub chain_type: ChainType,
+    /// PEM file signing transactions. Required on a real chain; the chain
+    /// simulator falls back to the `alice` test wallet.
+    #[serde(default)]
+    pub wallet_pem: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Self {
+        let content = std::fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("cannot read {}: {err}", path.display()));
+        let mut config: Config = toml::from_str(&content)
+            .unwrap_or_else(|err| panic!("invalid {}: {err}", path.display()));
+
+        // Relative wallet paths are relative to the config file, not the caller.
+        if let (Some(pem), Some(dir)) = (&config.wallet_pem, path.parent()) {
+            config.wallet_pem = Some(dir.join(pem));
+        }
+        config
+    }
+
+    pub fn chain_simulator_config() -> Self {
+        Config {
+            gateway_uri: CHAIN_SIMULATOR_GATEWAY.to_owned(),
+            chain_type: ChainType::Simulator,
+            wallet_pem: None,
+        }
+    }
+
+    pub fn use_chain_simulator(&self) -> bool {
+        self.chain_type == ChainType::Simulator
+    }
+}
@@ -0,0 +1,719 @@
+pub mod config;
+
+pub use public_commitment_fund_client::{parse_address, proxy};
+
+use config::Config;
+use multiversx_sc_snippets::{
+    imports::*,
+    sdk::gateway::{GetHyperBlockRequest, NetworkStatusRequest},
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+const STATE_FILE: &str = "state.toml";
+const CONTRACT_CODE_PATH: &str = "mxsc:../output/public-commitment-fund.mxsc.json";
+
+const DEPLOY_GAS: u64 = 80_000_000;
+const CALL_GAS: u64 = 30_000_000;
+
+/// Resolves `relative` against the interactor crate, so the binary behaves the
+/// same whatever directory it is started from.
+fn crate_path(relative: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(relative)
+}
+
+/// Address of the contract the interactor talks to, kept in `state.toml`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    contract_address: Option<Bech32Address>,
+}
+
+impl State {
+    pub fn load() -> Self {
+        match std::fs::read_to_string(crate_path(STATE_FILE)) {
+            Ok(content) => toml::from_str(&content).expect("invalid state file"),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        std::fs::write(crate_path(STATE_FILE), toml::to_string(self).unwrap())
+            .expect("cannot write state file");
+    }
+
+    pub fn set_address(&mut self, address: Bech32Address) {
+        self.contract_address = Some(address);
+        self.save();
+    }
+
+    pub fn current_address(&self) -> &Bech32Address {
+        self.contract_address
+            .as_ref()
+            .expect("no known contract, deploy first or pass --contract")
+    }
+}
+
+/// Optional `create_commitment` settings; unset ones take the contract defaults.
+#[derive(Debug, Default, Clone)]
+pub struct CreateOptions {
+    pub cooldown_seconds: Option<u64>,
+    pub claim_window_seconds: Option<u64>,
+    pub fallback: Option<Bech32Address>,
+    pub oracle: Option<Bech32Address>,
+    /// ed25519 public key of the attester, as an address.
+    pub attester: Option<Bech32Address>,
+    pub start_at: Option<u64>,
+    pub cancel_grace_seconds: Option<u64>,
+    pub cancel_penalty_bps: Option<u64>,
+    pub cancellable: Option<bool>,
+    /// No tags are sent when empty.
+    pub tags: Vec<String>,
+    pub description: Option<String>,
+    /// Set together with `metadata_hash`.
+    pub metadata_uri: Option<String>,
+    pub metadata_hash: Option<[u8; 32]>,
+}
+
+impl CreateOptions {
+    fn to_proxy(&self) -> proxy::CreateOptions<StaticApi> {
+        let address = |address: &Option<Bech32Address>| {
+            address.as_ref().map(|address| address.to_address().into())
+        };
+        let tags = (!self.tags.is_empty())
+            .then(|| self.tags.iter().map(|tag| ManagedBuffer::from(tag.as_str())).collect());
+
+        proxy::CreateOptions {
+            cooldown_seconds: self.cooldown_seconds,
+            claim_window_seconds: self.claim_window_seconds,
+            fallback: address(&self.fallback),
+            oracle: address(&self.oracle),
+            attester: address(&self.attester),
+            start_at: self.start_at,
+            cancel_grace_seconds: self.cancel_grace_seconds,
+            cancel_penalty_bps: self.cancel_penalty_bps,
+            cancellable: self.cancellable,
+            tags,
+            description: self.description.as_deref().map(ManagedBuffer::from),
+            metadata_uri: self.metadata_uri.as_deref().map(ManagedBuffer::from),
+            metadata_hash: self.metadata_hash.as_ref().map(ManagedByteArray::new_from_bytes),
+        }
+    }
+}
+
+pub struct ContractInteract {
+    pub interactor: Interactor,
+    pub wallet_address: Address,
+    contract_code: BytesValue,
+    pub state: State,
+}
+
+impl ContractInteract {
+    pub async fn new(config: Config) -> Self {
+        let wallet = match &config.wallet_pem {
+            Some(pem) => Wallet::from_pem_file(pem.to_str().expect("non UTF-8 wallet path"))
+                .unwrap_or_else(|err| panic!("cannot load wallet {}: {err}", pem.display())),
+            None if config.use_chain_simulator() => test_wallets::alice(),
+            None => panic!("wallet_pem (or --pem) is required unless chain_type is \"simulator\""),
+        };
+
+        let mut interactor = Interactor::new(&config.gateway_uri)
+            .await
+            .use_chain_simulator(config.use_chain_simulator());
+        interactor.set_current_dir_from_workspace("");
+
+        let wallet_address = interactor.register_wallet(wallet).await;
+
+        if config.use_chain_simulator() {
+            interactor.generate_blocks_until_all_activations().await;
+        }
+
+        let contract_code = BytesValue::interpret_from(
+            CONTRACT_CODE_PATH,
+            &InterpreterContext::new().with_dir(crate_path("")),
+        );
+
+        ContractInteract {
+            interactor,
+            wallet_address,
+            contract_code,
+            state: State::load(),
+        }
+    }
+
+    /// Timestamp of the latest metachain block. The contract checks deadlines
+    /// against block time, which on a simulator drifts from the host clock.
+    pub async fn now(&self) -> u64 {
+        let status = self
+            .interactor
+            .proxy
+            .http_request(NetworkStatusRequest::default())
+            .await
+            .expect("cannot read network status");
+        self.interactor
+            .proxy
+            .http_request(GetHyperBlockRequest::by_nonce(status.nonce))
+            .await
+            .expect("cannot read latest block")
+            .timestamp
+    }
+
+    pub async fn deploy(&mut self) -> Bech32Address {
+        let new_address = self
+            .interactor
+            .tx()
+            .from(&self.wallet_address)
+            .gas(DEPLOY_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .init()
+            .code(&self.contract_code)
+            .code_metadata(CodeMetadata::UPGRADEABLE)
+            .returns(ReturnsNewBech32Address)
+            .run()
+            .await;
+
+        self.state.set_address(new_address.clone());
+        new_address
+    }
+
+    pub async fn upgrade(&mut self) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(DEPLOY_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .upgrade()
+            .code(&self.contract_code)
+            .code_metadata(CodeMetadata::UPGRADEABLE)
+            .run()
+            .await;
+    }
+
+    pub async fn migrate_commitments(&mut self, ids: Vec<u64>) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .migrate_commitments(MultiValueVec::from(ids))
+            .run()
+            .await;
+    }
+
+    pub async fn set_archive_retention_seconds(&mut self, seconds: u64) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .set_archive_retention_seconds(seconds)
+            .run()
+            .await;
+    }
+
+    pub async fn set_curated_tags_only(&mut self, enabled: bool) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .set_curated_tags_only(enabled)
+            .run()
+            .await;
+    }
+
+    pub async fn add_allowed_tags(&mut self, tags: &[String]) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .add_allowed_tags(managed_tags(tags))
+            .run()
+            .await;
+    }
+
+    pub async fn remove_allowed_tags(&mut self, tags: &[String]) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .remove_allowed_tags(managed_tags(tags))
+            .run()
+            .await;
+    }
+
+    pub async fn create_commitment(
+        &mut self,
+        title: &str,
+        recipient: &Bech32Address,
+        deadline: u64,
+        amount: u128,
+        options: &CreateOptions,
+    ) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .create_commitment(title, recipient, deadline, OptionalValue::Some(options.to_proxy()))
+            .egld(BigUint::<StaticApi>::from(amount))
+            .run()
+            .await;
+    }
+
+    pub async fn submit_proof(&mut self, id: u64, proof_url: &str) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .submit_proof(id, proof_url)
+            .run()
+            .await;
+    }
+
+    pub async fn submit_typed_proof(
+        &mut self,
+        id: u64,
+        kind: proxy::ProofKind,
+        reference: &str,
+        evidence_hash: &[u8; 32],
+    ) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .submit_typed_proof(
+                id,
+                kind,
+                reference,
+                ManagedByteArray::new_from_bytes(evidence_hash),
+            )
+            .run()
+            .await;
+    }
+
+    pub async fn submit_attested_proof(&mut self, id: u64, proof_url: &str, signature: &[u8; 64]) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .submit_attested_proof(id, proof_url, ManagedByteArray::new_from_bytes(signature))
+            .run()
+            .await;
+    }
+
+    pub async fn request_oracle_verification(&mut self, id: u64) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .request_oracle_verification(id)
+            .run()
+            .await;
+    }
+
+    pub async fn submit_sealed_proof(&mut self, id: u64, proof_hash: &[u8; 32]) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .submit_sealed_proof(id, ManagedByteArray::new_from_bytes(proof_hash))
+            .run()
+            .await;
+    }
+
+    pub async fn reveal_proof(&mut self, id: u64, proof_url: &str, salt: &str) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .reveal_proof(id, proof_url, salt)
+            .run()
+            .await;
+    }
+
+    pub async fn finalize(&mut self, id: u64) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .finalize(id)
+            .run()
+            .await;
+    }
+
+    pub async fn claim(&mut self, id: u64) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .claim(id)
+            .run()
+            .await;
+    }
+
+    pub async fn claim_all(&mut self, limit: u64, start: Option<u64>) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .claim_all(limit, OptionalValue::from(start))
+            .run()
+            .await;
+    }
+
+    pub async fn release_to_recipient(&mut self, id: u64) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .release_to_recipient(id)
+            .run()
+            .await;
+    }
+
+    pub async fn set_release_endpoint(&mut self, endpoint: Option<&str>) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .set_release_endpoint(OptionalValue::from(endpoint))
+            .run()
+            .await;
+    }
+
+    pub async fn sweep(&mut self, id: u64) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .sweep(id)
+            .run()
+            .await;
+    }
+
+    pub async fn forgive(&mut self, id: u64, amount: Option<u128>) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .forgive(id, OptionalValue::from(amount.map(BigUint::<StaticApi>::from)))
+            .run()
+            .await;
+    }
+
+    pub async fn add_delegate(&mut self, kind: proxy::DelegationKind, delegate: &Bech32Address) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .add_delegate(kind, delegate)
+            .run()
+            .await;
+    }
+
+    pub async fn remove_delegate(&mut self, kind: proxy::DelegationKind, delegate: &Bech32Address) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .remove_delegate(kind, delegate)
+            .run()
+            .await;
+    }
+
+    pub async fn concede(&mut self, id: u64) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .concede(id)
+            .run()
+            .await;
+    }
+
+    pub async fn prune_commitments(&mut self, ids: Vec<u64>) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .prune_commitments(MultiValueVec::from(ids))
+            .run()
+            .await;
+    }
+
+    pub async fn cancel(&mut self, id: u64) {
+        self.interactor
+            .tx()
+            .from(&self.wallet_address)
+            .to(self.state.current_address())
+            .gas(CALL_GAS)
+            .typed(proxy::PublicCommitmentFundProxy)
+            .cancel(id)
+            .run()
+            .await;
+    }
+
+    pub async fn get_commitment(&mut self, id: u64) -> proxy::Commitment<StaticApi> {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_commitment(id)
+            .returns(ReturnsResult)
+            .run()
+            .await
+    }
+
+    pub async fn get_proof_history(&mut self, id: u64) -> Vec<proxy::ProofEntry<StaticApi>> {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_proof_history(id)
+            .returns(ReturnsResult)
+            .run()
+            .await
+            .into_iter()
+            .collect()
+    }
+
+    pub async fn get_attestation_message(&mut self, id: u64, proof_url: &str) -> Vec<u8> {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_attestation_message(id, proof_url)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+
+    pub async fn get_total_ids(&mut self) -> u64 {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_total_ids()
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+
+    pub async fn get_ids_page(&mut self, start: u64, limit: u64) -> Vec<u64> {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_ids_page(start, limit)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+            .into_vec()
+    }
+
+    pub async fn get_total_open_ids(&mut self, recipient: &Bech32Address) -> u64 {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_total_open_ids(recipient)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+
+    pub async fn get_ids_by_tag(&mut self, tag: &str, start: u64, limit: u64) -> Vec<u64> {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_ids_by_tag(tag, start, limit)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+            .into_vec()
+    }
+
+    pub async fn get_total_ids_by_tag(&mut self, tag: &str) -> u64 {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_total_ids_by_tag(tag)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+
+    pub async fn get_release_endpoint(&mut self, recipient: &Bech32Address) -> String {
+        let endpoint = self
+            .interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_release_endpoint(recipient)
+            .returns(ReturnsResult)
+            .run()
+            .await;
+        String::from_utf8_lossy(&endpoint.to_vec()).into_owned()
+    }
+
+    pub async fn get_delegates(
+        &mut self,
+        owner: &Bech32Address,
+        kind: proxy::DelegationKind,
+    ) -> Vec<Bech32Address> {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_delegates(owner, kind)
+            .returns(ReturnsResult)
+            .run()
+            .await
+            .into_iter()
+            .map(|delegate| Bech32Address::from(delegate.to_address()))
+            .collect()
+    }
+
+    pub async fn is_delegate(
+        &mut self,
+        owner: &Bech32Address,
+        kind: proxy::DelegationKind,
+        delegate: &Bech32Address,
+    ) -> bool {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .is_delegate(owner, kind, delegate)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+
+    pub async fn get_archived_commitment(
+        &mut self,
+        id: u64,
+    ) -> Option<proxy::ArchivedCommitment<StaticApi>> {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_archived_commitment(id)
+            .returns(ReturnsResult)
+            .run()
+            .await
+            .into_option()
+    }
+
+    pub async fn is_pruned(&mut self, id: u64) -> bool {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .is_pruned(id)
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+
+    pub async fn get_archive_retention_seconds(&mut self) -> u64 {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_archive_retention_seconds()
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+
+    pub async fn is_curated_tags_only(&mut self) -> bool {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .is_curated_tags_only()
+            .returns(ReturnsResultUnmanaged)
+            .run()
+            .await
+    }
+
+    pub async fn get_allowed_tags(&mut self) -> Vec<String> {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_allowed_tags()
+            .returns(ReturnsResult)
+            .run()
+            .await
+            .into_iter()
+            .map(|tag| String::from_utf8_lossy(tag.to_boxed_bytes().as_slice()).into_owned())
+            .collect()
+    }
+
+    pub async fn get_commitments_batch(
+        &mut self,
+        ids: Vec<u64>,
+    ) -> Vec<proxy::Commitment<StaticApi>> {
+        self.interactor
+            .query()
+            .to(self.state.current_address())
+            .typed(proxy::PublicCommitmentFundProxy)
+            .get_commitments_batch(MultiValueVec::from(ids))
+            .returns(ReturnsResult)
+            .run()
+            .await
+            .into_iter()
+            .collect()
+    }
+}
+
+fn managed_tags(tags: &[String]) -> MultiValueEncoded<StaticApi, ManagedBuffer<StaticApi>> {
+    tags.iter().map(|tag| ManagedBuffer::from(tag.as_str())).collect()
+}
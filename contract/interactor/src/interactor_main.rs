@@ -0,0 +1,485 @@
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use multiversx_sc_snippets::imports::*;
+use public_commitment_fund_interactor::{
+    config::{ChainType, Config},
+    parse_address, proxy, ContractInteract, CreateOptions,
+};
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[command(about = "Deploys and calls the public commitment fund contract")]
+struct Cli {
+    /// Config file with the gateway and wallet to use.
+    #[arg(long, default_value = concat!(env!("CARGO_MANIFEST_DIR"), "/config.toml"))]
+    config: PathBuf,
+    /// Overrides the config's gateway URI.
+    #[arg(long)]
+    gateway: Option<String>,
+    /// Overrides the config's wallet PEM file.
+    #[arg(long)]
+    pem: Option<PathBuf>,
+    /// Targets a local chain simulator instead of a real network.
+    #[arg(long)]
+    simulator: bool,
+    /// Contract address to use instead of the last deployed one.
+    #[arg(long)]
+    contract: Option<String>,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    Deploy,
+    Upgrade,
+    CreateCommitment(Box<CreateArgs>),
+    MigrateCommitments {
+        ids: Vec<u64>,
+    },
+    SetArchiveRetentionSeconds {
+        seconds: u64,
+    },
+    SetCuratedTagsOnly {
+        enabled: bool,
+    },
+    AddAllowedTags {
+        tags: Vec<String>,
+    },
+    RemoveAllowedTags {
+        tags: Vec<String>,
+    },
+    SubmitProof {
+        id: u64,
+        proof_url: String,
+    },
+    SubmitTypedProof {
+        id: u64,
+        kind: ProofKind,
+        reference: String,
+        /// Hex-encoded 32-byte hash of the evidence `reference` points at.
+        evidence_hash: String,
+    },
+    SubmitAttestedProof {
+        id: u64,
+        proof_url: String,
+        /// Hex-encoded ed25519 signature over `get-attestation-message`.
+        signature: String,
+    },
+    RequestOracleVerification {
+        id: u64,
+    },
+    SubmitSealedProof {
+        id: u64,
+        /// Hex-encoded sha256 of the proof URL followed by the salt.
+        proof_hash: String,
+    },
+    RevealProof {
+        id: u64,
+        proof_url: String,
+        salt: String,
+    },
+    Finalize {
+        id: u64,
+    },
+    Claim {
+        id: u64,
+    },
+    ClaimAll {
+        limit: u64,
+        /// Position in the caller's open commitments to start from.
+        #[arg(long)]
+        start: Option<u64>,
+    },
+    ReleaseToRecipient {
+        id: u64,
+    },
+    /// Sets the endpoint `release-to-recipient` calls on the calling
+    /// contract, or clears it when omitted.
+    SetReleaseEndpoint {
+        endpoint: Option<String>,
+    },
+    Sweep {
+        id: u64,
+    },
+    Forgive {
+        id: u64,
+        /// Amount to waive, in wei; all of the stake when omitted.
+        #[arg(long)]
+        amount: Option<u128>,
+    },
+    AddDelegate {
+        kind: DelegationKind,
+        delegate: String,
+    },
+    RemoveDelegate {
+        kind: DelegationKind,
+        delegate: String,
+    },
+    Cancel {
+        id: u64,
+    },
+    Concede {
+        id: u64,
+    },
+    PruneCommitments {
+        ids: Vec<u64>,
+    },
+    GetCommitment {
+        id: u64,
+    },
+    GetProofHistory {
+        id: u64,
+    },
+    GetAttestationMessage {
+        id: u64,
+        proof_url: String,
+    },
+    GetTotalIds,
+    GetIdsPage {
+        start: u64,
+        limit: u64,
+    },
+    GetIdsByTag {
+        tag: String,
+        start: u64,
+        limit: u64,
+    },
+    GetTotalIdsByTag {
+        tag: String,
+    },
+    GetTotalOpenIds {
+        recipient: String,
+    },
+    GetReleaseEndpoint {
+        recipient: String,
+    },
+    GetDelegates {
+        owner: String,
+        kind: DelegationKind,
+    },
+    IsDelegate {
+        owner: String,
+        kind: DelegationKind,
+        delegate: String,
+    },
+    GetArchivedCommitment {
+        id: u64,
+    },
+    IsPruned {
+        id: u64,
+    },
+    GetArchiveRetentionSeconds,
+    IsCuratedTagsOnly,
+    GetAllowedTags,
+    GetCommitmentsBatch {
+        ids: Vec<u64>,
+    },
+}
+
+#[derive(Args)]
+struct CreateArgs {
+    #[arg(long)]
+    title: String,
+    #[arg(long)]
+    recipient: String,
+    /// Unix timestamp, in seconds.
+    #[arg(long)]
+    deadline: u64,
+    /// Stake, in wei (1 EGLD = 10^18).
+    #[arg(long)]
+    amount: u128,
+    #[arg(long)]
+    cooldown_seconds: Option<u64>,
+    #[arg(long)]
+    claim_window_seconds: Option<u64>,
+    #[arg(long)]
+    fallback: Option<String>,
+    #[arg(long)]
+    oracle: Option<String>,
+    #[arg(long)]
+    attester: Option<String>,
+    /// Unix timestamp, in seconds, from which proofs are accepted.
+    #[arg(long)]
+    start_at: Option<u64>,
+    #[arg(long)]
+    cancel_grace_seconds: Option<u64>,
+    #[arg(long)]
+    cancel_penalty_bps: Option<u64>,
+    #[arg(long)]
+    cancellable: Option<bool>,
+    /// Repeat for several tags.
+    #[arg(long = "tag")]
+    tags: Vec<String>,
+    #[arg(long)]
+    description: Option<String>,
+    #[arg(long, requires = "metadata_hash")]
+    metadata_uri: Option<String>,
+    /// Hex-encoded sha256 of the document at `--metadata-uri`.
+    #[arg(long, requires = "metadata_uri")]
+    metadata_hash: Option<String>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum DelegationKind {
+    Claim,
+    Proof,
+}
+
+/// The proof kinds callers can submit with `submit-typed-proof`.
+#[derive(Clone, Copy, ValueEnum)]
+enum ProofKind {
+    HttpUrl,
+    IpfsCid,
+    ContentHash,
+    TxHash,
+}
+
+impl From<ProofKind> for proxy::ProofKind {
+    fn from(kind: ProofKind) -> Self {
+        match kind {
+            ProofKind::HttpUrl => proxy::ProofKind::HttpUrl,
+            ProofKind::IpfsCid => proxy::ProofKind::IpfsCid,
+            ProofKind::ContentHash => proxy::ProofKind::ContentHash,
+            ProofKind::TxHash => proxy::ProofKind::TxHash,
+        }
+    }
+}
+
+impl From<DelegationKind> for proxy::DelegationKind {
+    fn from(kind: DelegationKind) -> Self {
+        match kind {
+            DelegationKind::Claim => proxy::DelegationKind::Claim,
+            DelegationKind::Proof => proxy::DelegationKind::Proof,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    env_logger::init();
+
+    if let Err(err) = run(Cli::parse()).await {
+        eprintln!("error: {err}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), String> {
+    let mut config = Config::load(&cli.config);
+    if let Some(gateway) = cli.gateway {
+        config.gateway_uri = gateway;
+    }
+    if let Some(pem) = cli.pem {
+        config.wallet_pem = Some(pem);
+    }
+    if cli.simulator {
+        config.chain_type = ChainType::Simulator;
+    }
+
+    let contract = cli.contract.as_deref().map(parse_address).transpose()?;
+
+    let mut interact = ContractInteract::new(config).await;
+    if let Some(contract) = contract {
+        interact.state.set_address(contract);
+    }
+
+    match cli.command {
+        Command::Deploy => println!("new address: {}", interact.deploy().await),
+        Command::Upgrade => interact.upgrade().await,
+        Command::CreateCommitment(args) => {
+            let CreateArgs {
+                title,
+                recipient,
+                deadline,
+                amount,
+                cooldown_seconds,
+                claim_window_seconds,
+                fallback,
+                oracle,
+                attester,
+                start_at,
+                cancel_grace_seconds,
+                cancel_penalty_bps,
+                cancellable,
+                tags,
+                description,
+                metadata_uri,
+                metadata_hash,
+            } = *args;
+            let options = CreateOptions {
+                cooldown_seconds,
+                claim_window_seconds,
+                fallback: fallback.as_deref().map(parse_address).transpose()?,
+                oracle: oracle.as_deref().map(parse_address).transpose()?,
+                attester: attester.as_deref().map(parse_address).transpose()?,
+                start_at,
+                cancel_grace_seconds,
+                cancel_penalty_bps,
+                cancellable,
+                tags,
+                description,
+                metadata_uri,
+                metadata_hash: metadata_hash
+                    .map(|hash| hex_bytes(&hash, "metadata hash"))
+                    .transpose()?,
+            };
+            let recipient = parse_address(&recipient)?;
+            interact
+                .create_commitment(&title, &recipient, deadline, amount, &options)
+                .await;
+            println!("created commitment {}", interact.get_total_ids().await);
+        },
+        Command::MigrateCommitments { ids } => interact.migrate_commitments(ids).await,
+        Command::SetArchiveRetentionSeconds { seconds } => {
+            interact.set_archive_retention_seconds(seconds).await
+        },
+        Command::SetCuratedTagsOnly { enabled } => interact.set_curated_tags_only(enabled).await,
+        Command::AddAllowedTags { tags } => interact.add_allowed_tags(&tags).await,
+        Command::RemoveAllowedTags { tags } => interact.remove_allowed_tags(&tags).await,
+        Command::SubmitProof { id, proof_url } => interact.submit_proof(id, &proof_url).await,
+        Command::SubmitTypedProof { id, kind, reference, evidence_hash } => {
+            let evidence_hash = hex_bytes(&evidence_hash, "evidence hash")?;
+            interact
+                .submit_typed_proof(id, kind.into(), &reference, &evidence_hash)
+                .await
+        },
+        Command::SubmitAttestedProof { id, proof_url, signature } => {
+            let signature = hex_bytes(&signature, "signature")?;
+            interact.submit_attested_proof(id, &proof_url, &signature).await
+        },
+        Command::RequestOracleVerification { id } => interact.request_oracle_verification(id).await,
+        Command::SubmitSealedProof { id, proof_hash } => {
+            let proof_hash = hex_bytes(&proof_hash, "proof hash")?;
+            interact.submit_sealed_proof(id, &proof_hash).await
+        },
+        Command::RevealProof { id, proof_url, salt } => {
+            interact.reveal_proof(id, &proof_url, &salt).await
+        },
+        Command::Finalize { id } => interact.finalize(id).await,
+        Command::Claim { id } => interact.claim(id).await,
+        Command::ClaimAll { limit, start } => interact.claim_all(limit, start).await,
+        Command::ReleaseToRecipient { id } => interact.release_to_recipient(id).await,
+        Command::SetReleaseEndpoint { endpoint } => {
+            interact.set_release_endpoint(endpoint.as_deref()).await
+        },
+        Command::Sweep { id } => interact.sweep(id).await,
+        Command::Forgive { id, amount } => interact.forgive(id, amount).await,
+        Command::AddDelegate { kind, delegate } => {
+            let delegate = parse_address(&delegate)?;
+            interact.add_delegate(kind.into(), &delegate).await
+        },
+        Command::RemoveDelegate { kind, delegate } => {
+            let delegate = parse_address(&delegate)?;
+            interact.remove_delegate(kind.into(), &delegate).await
+        },
+        Command::Cancel { id } => interact.cancel(id).await,
+        Command::Concede { id } => interact.concede(id).await,
+        Command::PruneCommitments { ids } => interact.prune_commitments(ids).await,
+        Command::GetCommitment { id } => print_commitment(&interact.get_commitment(id).await),
+        Command::GetProofHistory { id } => {
+            for entry in interact.get_proof_history(id).await {
+                println!(
+                    "{} {:?} {} {}",
+                    entry.submitted_at,
+                    entry.kind,
+                    utf8(&entry.reference),
+                    hex(&entry.proof_hash),
+                );
+            }
+        },
+        Command::GetAttestationMessage { id, proof_url } => {
+            let message = interact.get_attestation_message(id, &proof_url).await;
+            println!("{}", hex::encode(message));
+        },
+        Command::GetTotalIds => println!("{}", interact.get_total_ids().await),
+        Command::GetIdsPage { start, limit } => {
+            println!("{:?}", interact.get_ids_page(start, limit).await)
+        },
+        Command::GetIdsByTag { tag, start, limit } => {
+            println!("{:?}", interact.get_ids_by_tag(&tag, start, limit).await)
+        },
+        Command::GetTotalIdsByTag { tag } => {
+            println!("{}", interact.get_total_ids_by_tag(&tag).await)
+        },
+        Command::GetTotalOpenIds { recipient } => {
+            let recipient = parse_address(&recipient)?;
+            println!("{}", interact.get_total_open_ids(&recipient).await)
+        },
+        Command::GetReleaseEndpoint { recipient } => {
+            let recipient = parse_address(&recipient)?;
+            println!("{}", interact.get_release_endpoint(&recipient).await)
+        },
+        Command::GetDelegates { owner, kind } => {
+            let owner = parse_address(&owner)?;
+            for delegate in interact.get_delegates(&owner, kind.into()).await {
+                println!("{delegate}");
+            }
+        },
+        Command::IsDelegate { owner, kind, delegate } => {
+            let owner = parse_address(&owner)?;
+            let delegate = parse_address(&delegate)?;
+            println!("{}", interact.is_delegate(&owner, kind.into(), &delegate).await)
+        },
+        Command::GetArchivedCommitment { id } => match interact.get_archived_commitment(id).await {
+            Some(archived) => {
+                println!("commitment {} (pruned)", archived.id);
+                println!("  status:      {:?}", archived.status);
+                println!("  creator:     {}", bech32(&archived.creator));
+                println!("  recipient:   {}", bech32(&archived.recipient));
+                println!("  amount:      {}", archived.amount.to_display());
+                println!("  settled_at:  {}", archived.settled_at);
+                println!("  record_hash: {}", hex(&archived.record_hash));
+            },
+            None => println!("commitment {id} is not pruned"),
+        },
+        Command::IsPruned { id } => println!("{}", interact.is_pruned(id).await),
+        Command::GetArchiveRetentionSeconds => {
+            println!("{}", interact.get_archive_retention_seconds().await)
+        },
+        Command::IsCuratedTagsOnly => println!("{}", interact.is_curated_tags_only().await),
+        Command::GetAllowedTags => println!("{:?}", interact.get_allowed_tags().await),
+        Command::GetCommitmentsBatch { ids } => {
+            for commitment in interact.get_commitments_batch(ids).await {
+                print_commitment(&commitment);
+            }
+        },
+    }
+    Ok(())
+}
+
+/// Decodes a hex argument of exactly `N` bytes.
+fn hex_bytes<const N: usize>(value: &str, what: &str) -> Result<[u8; N], String> {
+    let bytes = hex::decode(value).map_err(|err| format!("{what} is not hex: {err}"))?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| format!("{what} is {} bytes, expected {N}", bytes.len()))
+}
+
+fn print_commitment(commitment: &proxy::Commitment<StaticApi>) {
+    let tags: Vec<String> = commitment.tags.iter().map(|tag| utf8(&tag)).collect();
+    println!("commitment {}", commitment.id);
+    println!("  status:      {:?}", commitment.status);
+    println!("  title:       {}", utf8(&commitment.title));
+    println!("  creator:     {}", bech32(&commitment.creator));
+    println!("  recipient:   {}", bech32(&commitment.recipient));
+    println!("  amount:      {}", commitment.amount.to_display());
+    println!("  start_at:    {}", commitment.start_at);
+    println!("  deadline:    {}", commitment.deadline);
+    println!("  cooldown:    {}s", commitment.cooldown_seconds);
+    println!("  proof:       {:?} {}", commitment.proof_kind, utf8(&commitment.proof_url));
+    println!("  proof_hash:  {}", hex(&commitment.proof_hash));
+    println!("  finalized:   {}", commitment.finalized_at);
+    println!("  tags:        {}", tags.join(", "));
+}
+
+fn utf8(buffer: &ManagedBuffer<StaticApi>) -> String {
+    String::from_utf8_lossy(&buffer.to_boxed_bytes().into_vec()).into_owned()
+}
+
+fn hex(buffer: &ManagedBuffer<StaticApi>) -> String {
+    hex::encode(buffer.to_boxed_bytes().as_slice())
+}
+
+fn bech32(address: &ManagedAddress<StaticApi>) -> String {
+    Bech32Address::from(address.to_address()).to_bech32_string()
+}
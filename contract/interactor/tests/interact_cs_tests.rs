@@ -0,0 +1,40 @@
+use multiversx_sc_snippets::imports::*;
+use public_commitment_fund_interactor::{
+    config::Config, proxy::CommitmentStatus, ContractInteract, CreateOptions,
+};
+
+const ONE_EGLD: u128 = 1_000_000_000_000_000_000;
+
+// Needs a chain simulator listening on localhost:8085 and the contract built
+// (`cargo run -- build` in contract/meta). Run with
+// `cargo test --features chain-simulator-tests`.
+#[tokio::test]
+#[cfg_attr(not(feature = "chain-simulator-tests"), ignore)]
+async fn deploy_create_prove_and_cancel_cs() {
+    let mut interact = ContractInteract::new(Config::chain_simulator_config()).await;
+    interact.deploy().await;
+
+    let recipient = Bech32Address::from(test_wallets::bob().to_address());
+    let deadline = interact.now().await + 3_600;
+    for _ in 0..2 {
+        interact
+            .create_commitment(
+                "Ship weekly report",
+                &recipient,
+                deadline,
+                ONE_EGLD,
+                &CreateOptions::default(),
+            )
+            .await;
+    }
+    assert_eq!(interact.get_total_ids().await, 2);
+    assert_eq!(interact.get_ids_page(0, 10).await, vec![1, 2]);
+
+    interact.submit_proof(1, "https://example.com/proof").await;
+    interact.cancel(2).await;
+
+    let commitments = interact.get_commitments_batch(vec![1, 2]).await;
+    assert_eq!(commitments[0].status, CommitmentStatus::Completed);
+    assert_eq!(commitments[1].status, CommitmentStatus::Refunded);
+    assert_eq!(interact.get_proof_history(1).await.len(), 1);
+}
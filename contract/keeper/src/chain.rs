@@ -0,0 +1,119 @@
+use anyhow::{bail, Context};
+use multiversx_sc::codec::{top_encode_to_vec_u8_or_panic, TopDecode, TopEncode};
+use multiversx_sc_snippets::{
+    imports::{Bech32Address, GatewayHttpProxy, StaticApi},
+    sdk::{
+        data::{network_config::NetworkConfig, transaction::Transaction, vm::VMQueryInput},
+        gateway::{
+            ChainSimulatorGenerateBlocksRequest, GetAccountRequest, GetHyperBlockRequest,
+            GetTxProcessStatus, NetworkConfigRequest, NetworkStatusRequest, SendTxRequest,
+            VMQueryRequest,
+        },
+    },
+};
+use public_commitment_fund_client::{proxy::Commitment, DecodedCommitment};
+
+/// Outcome of a sent transaction, from the gateway's process status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TxOutcome {
+    Pending,
+    Success,
+    Failed(String),
+}
+
+/// Thin gateway client for the calls the keeper makes. Every method returns
+/// errors instead of panicking, so a flaky gateway only costs one round.
+pub struct Chain {
+    proxy: GatewayHttpProxy,
+    pub contract: Bech32Address,
+}
+
+impl Chain {
+    pub fn new(gateway_uri: &str, contract: Bech32Address) -> Self {
+        Chain {
+            proxy: GatewayHttpProxy::new(gateway_uri.trim_end_matches('/').to_owned()),
+            contract,
+        }
+    }
+
+    pub async fn network_config(&self) -> anyhow::Result<NetworkConfig> {
+        self.proxy.http_request(NetworkConfigRequest).await
+    }
+
+    /// Timestamp of the latest metachain block. The contract compares deadlines
+    /// with block time, so the keeper does too rather than trusting its clock.
+    pub async fn now(&self) -> anyhow::Result<u64> {
+        let status = self.proxy.http_request(NetworkStatusRequest::default()).await?;
+        let block = self.proxy.http_request(GetHyperBlockRequest::by_nonce(status.nonce)).await?;
+        Ok(block.timestamp)
+    }
+
+    pub async fn account_nonce(&self, address: &Bech32Address) -> anyhow::Result<u64> {
+        let account = self.proxy.http_request(GetAccountRequest::new(address)).await?;
+        Ok(account.nonce)
+    }
+
+    /// Sends a signed transaction and returns its hash.
+    pub async fn send(&self, tx: &Transaction) -> anyhow::Result<String> {
+        self.proxy.http_request(SendTxRequest(tx)).await
+    }
+
+    pub async fn tx_outcome(&self, hash: &str) -> anyhow::Result<TxOutcome> {
+        let (status, reason) = self.proxy.http_request(GetTxProcessStatus::new(hash)).await?;
+        Ok(match status.as_str() {
+            "success" => TxOutcome::Success,
+            "fail" | "invalid" => TxOutcome::Failed(reason),
+            _ => TxOutcome::Pending,
+        })
+    }
+
+    /// Chain simulator only: blocks are produced on request, not over time.
+    pub async fn generate_blocks(&self, count: u64) -> anyhow::Result<()> {
+        self.proxy.http_request(ChainSimulatorGenerateBlocksRequest::num_blocks(count)).await?;
+        Ok(())
+    }
+
+    async fn query(&self, func_name: &str, args: &[Vec<u8>]) -> anyhow::Result<Vec<Vec<u8>>> {
+        let input = VMQueryInput {
+            sc_address: self.contract.clone(),
+            func_name: func_name.to_owned(),
+            args: args.iter().map(hex::encode).collect(),
+        };
+        let output = self.proxy.http_request(VMQueryRequest(&input)).await?.data;
+        if !output.is_ok() {
+            bail!("{func_name} failed: {}", output.return_message);
+        }
+        Ok(output.return_data_base64_decode())
+    }
+
+    pub async fn total_ids(&self) -> anyhow::Result<u64> {
+        let result = self.query("get_total_ids", &[]).await?;
+        decode_all::<u64>(&result).map(|values| values.first().copied().unwrap_or_default())
+    }
+
+    pub async fn ids_page(&self, start: u64, limit: u64) -> anyhow::Result<Vec<u64>> {
+        let result = self.query("get_ids_page", &[encode(&start), encode(&limit)]).await?;
+        decode_all(&result)
+    }
+
+    pub async fn commitments(&self, ids: &[u64]) -> anyhow::Result<Vec<DecodedCommitment>> {
+        let args: Vec<_> = ids.iter().map(encode).collect();
+        let result = self.query("get_commitments_batch", &args).await?;
+        let commitments = decode_all::<Commitment<StaticApi>>(&result)?;
+        Ok(commitments.iter().map(DecodedCommitment::from).collect())
+    }
+}
+
+fn encode<T: TopEncode>(value: &T) -> Vec<u8> {
+    top_encode_to_vec_u8_or_panic(value)
+}
+
+fn decode_all<T: TopDecode>(values: &[Vec<u8>]) -> anyhow::Result<Vec<T>> {
+    values
+        .iter()
+        .map(|value| {
+            T::top_decode(value.as_slice()).map_err(|err| anyhow::anyhow!("{err:?}"))
+        })
+        .collect::<anyhow::Result<_>>()
+        .context("unexpected view result")
+}
@@ -0,0 +1,193 @@
+pub mod chain;
+pub mod sender;
+
+use chain::{Chain, TxOutcome};
+use multiversx_sc::codec::top_encode_to_vec_u8_or_panic;
+use multiversx_sc_snippets::{
+    imports::{tokio, Bech32Address},
+    log,
+    sdk::wallet::Wallet,
+};
+use public_commitment_fund_client::{CommitmentStatus, REVEAL_WINDOW_SECONDS};
+use sender::Sender;
+use std::time::Duration;
+
+const FINALIZE_GAS: u64 = 30_000_000;
+const PAGE_SIZE: u64 = 50;
+const STATUS_POLL: Duration = Duration::from_secs(3);
+const STATUS_POLLS: u32 = 40;
+
+/// First timestamp at which `finalize` accepts a commitment, or `None` if it
+/// never will (already settled, or waiting on an oracle).
+pub fn finalizable_after(status: CommitmentStatus, deadline: u64) -> Option<u64> {
+    match status {
+        CommitmentStatus::Active | CommitmentStatus::Completed => Some(deadline + 1),
+        CommitmentStatus::Sealed => Some(deadline + REVEAL_WINDOW_SECONDS + 1),
+        _ => None,
+    }
+}
+
+/// Whether `finalize` may still accept a commitment, now or later. `Verifying`
+/// goes back to `Active` when the oracle call fails.
+pub fn may_become_due(status: CommitmentStatus) -> bool {
+    matches!(
+        status,
+        CommitmentStatus::Active
+            | CommitmentStatus::Completed
+            | CommitmentStatus::Sealed
+            | CommitmentStatus::Verifying
+    )
+}
+
+pub struct KeeperConfig {
+    pub gateway_uri: String,
+    pub contract: Bech32Address,
+    /// Signs `finalize` calls; may only be `None` in dry-run mode.
+    pub wallet: Option<Wallet>,
+    /// Lists due commitments without sending anything.
+    pub dry_run: bool,
+    /// Produces blocks while waiting for transactions, as a chain simulator
+    /// does not advance on its own.
+    pub chain_simulator: bool,
+    pub retries: u32,
+    pub retry_delay: Duration,
+}
+
+/// What one round found and did.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub due: Vec<u64>,
+    pub finalized: Vec<u64>,
+    /// Ids whose `finalize` was rejected or could not be sent, with the reason.
+    pub failed: Vec<(u64, String)>,
+}
+
+pub struct Keeper {
+    chain: Chain,
+    sender: Option<Sender>,
+    dry_run: bool,
+    chain_simulator: bool,
+    /// Position in `get_ids_page` before which every commitment is settled,
+    /// so scans start there instead of at the first id ever created.
+    low_water: u64,
+}
+
+impl Keeper {
+    pub async fn new(config: KeeperConfig) -> anyhow::Result<Self> {
+        let chain = Chain::new(&config.gateway_uri, config.contract);
+        let sender = match (config.wallet, config.dry_run) {
+            (Some(wallet), _) => {
+                Some(Sender::new(&chain, wallet, config.retries, config.retry_delay).await?)
+            },
+            (None, true) => None,
+            (None, false) => anyhow::bail!("a wallet is required unless running dry"),
+        };
+
+        Ok(Keeper {
+            chain,
+            sender,
+            dry_run: config.dry_run,
+            chain_simulator: config.chain_simulator,
+            low_water: 0,
+        })
+    }
+
+    pub fn low_water(&self) -> u64 {
+        self.low_water
+    }
+
+    /// Ids of every commitment `finalize` would accept right now. Scans from
+    /// the low-water mark and moves it past the settled commitments at the
+    /// front, pruned ones included.
+    pub async fn due_ids(&mut self) -> anyhow::Result<Vec<u64>> {
+        let now = self.chain.now().await?;
+        let total = self.chain.total_ids().await?;
+
+        let mut due = Vec::new();
+        let mut start = self.low_water;
+        let mut settled_so_far = true;
+        while start < total {
+            let ids = self.chain.ids_page(start, PAGE_SIZE).await?;
+            if ids.is_empty() {
+                break;
+            }
+
+            // Pruned commitments are left out of the batch.
+            let commitments = self.chain.commitments(&ids).await?;
+            for (position, id) in (start..).zip(&ids) {
+                let commitment = commitments.iter().find(|commitment| commitment.id == *id);
+                let open = commitment.is_some_and(|commitment| may_become_due(commitment.status));
+                if settled_so_far && !open {
+                    self.low_water = position + 1;
+                } else {
+                    settled_so_far = false;
+                }
+
+                let Some(commitment) = commitment else {
+                    continue;
+                };
+                let after = finalizable_after(commitment.status, commitment.deadline);
+                if after.is_some_and(|after| now >= after) {
+                    due.push(commitment.id);
+                }
+            }
+            start += ids.len() as u64;
+        }
+        Ok(due)
+    }
+
+    /// Scans for due commitments and, unless running dry, finalizes them: all
+    /// calls are sent first, with consecutive nonces, then awaited together.
+    pub async fn run_once(&mut self) -> anyhow::Result<Report> {
+        let mut report = Report {
+            due: self.due_ids().await?,
+            ..Report::default()
+        };
+        let Some(sender) = self.sender.as_mut().filter(|_| !self.dry_run) else {
+            return Ok(report);
+        };
+
+        let mut pending = Vec::new();
+        for &id in &report.due {
+            let data = format!("finalize@{}", hex::encode(top_encode_to_vec_u8_or_panic(&id)));
+            match sender.call(&self.chain, &data, FINALIZE_GAS).await {
+                Ok(hash) => {
+                    log::info!("finalize({id}) sent in {hash}");
+                    pending.push((id, hash));
+                },
+                Err(err) => report.failed.push((id, format!("{err:#}"))),
+            }
+        }
+
+        for _ in 0..STATUS_POLLS {
+            if pending.is_empty() {
+                break;
+            }
+            if self.chain_simulator {
+                self.chain.generate_blocks(1).await?;
+            } else {
+                tokio::time::sleep(STATUS_POLL).await;
+            }
+
+            let mut still_pending = Vec::new();
+            for (id, hash) in pending {
+                match self.chain.tx_outcome(&hash).await {
+                    Ok(TxOutcome::Success) => report.finalized.push(id),
+                    Ok(TxOutcome::Failed(reason)) => report.failed.push((id, reason)),
+                    Ok(TxOutcome::Pending) | Err(_) => still_pending.push((id, hash)),
+                }
+            }
+            pending = still_pending;
+        }
+
+        if !pending.is_empty() {
+            // Unconfirmed transactions may have been dropped; their nonces are
+            // reused next round.
+            sender.resync_nonce();
+            for (id, hash) in pending {
+                report.failed.push((id, format!("{hash} still pending")));
+            }
+        }
+        Ok(report)
+    }
+}
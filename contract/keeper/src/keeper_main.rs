@@ -0,0 +1,76 @@
+use clap::Parser;
+use multiversx_sc_snippets::{env_logger, imports::tokio, log, sdk::wallet::Wallet};
+use public_commitment_fund_client::parse_address;
+use public_commitment_fund_keeper::{Keeper, KeeperConfig};
+use std::{path::PathBuf, time::Duration};
+
+#[derive(Parser)]
+#[command(about = "Finalizes public commitment fund commitments once they are due")]
+struct Cli {
+    /// Bech32 address of the contract to keep.
+    #[arg(long)]
+    contract: String,
+    #[arg(long, default_value = "https://devnet-gateway.multiversx.com")]
+    gateway: String,
+    /// PEM file signing `finalize` calls; required unless `--dry-run`.
+    #[arg(long, required_unless_present = "dry_run")]
+    pem: Option<PathBuf>,
+    /// Only lists the commitments that are due.
+    #[arg(long)]
+    dry_run: bool,
+    /// Targets a local chain simulator, producing blocks while waiting.
+    #[arg(long)]
+    simulator: bool,
+    /// Send attempts per transaction after the first one fails.
+    #[arg(long, default_value_t = 3)]
+    retries: u32,
+    /// Delay before the first retry; doubles on each attempt.
+    #[arg(long, default_value_t = 2)]
+    retry_delay_seconds: u64,
+    /// Seconds between scans.
+    #[arg(long, default_value_t = 60)]
+    interval_seconds: u64,
+    /// Exits after one scan instead of looping.
+    #[arg(long)]
+    once: bool,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    env_logger::init();
+
+    let cli = Cli::parse();
+    let contract = parse_address(&cli.contract).map_err(anyhow::Error::msg)?;
+    let wallet = match &cli.pem {
+        Some(pem) => Some(Wallet::from_pem_file(pem.to_str().expect("non UTF-8 wallet path"))?),
+        None => None,
+    };
+    let mut keeper = Keeper::new(KeeperConfig {
+        gateway_uri: cli.gateway,
+        contract,
+        wallet,
+        dry_run: cli.dry_run,
+        chain_simulator: cli.simulator,
+        retries: cli.retries,
+        retry_delay: Duration::from_secs(cli.retry_delay_seconds),
+    })
+    .await?;
+
+    loop {
+        match keeper.run_once().await {
+            Ok(report) if cli.dry_run => println!("due: {:?}", report.due),
+            Ok(report) => {
+                println!("due: {:?}, finalized: {:?}", report.due, report.finalized);
+                for (id, reason) in &report.failed {
+                    println!("finalize({id}) failed: {reason}");
+                }
+            },
+            // A failed scan is retried on the next round rather than ending the bot.
+            Err(err) => log::error!("scan failed: {err:#}"),
+        }
+        if cli.once {
+            return Ok(());
+        }
+        tokio::time::sleep(Duration::from_secs(cli.interval_seconds)).await;
+    }
+}
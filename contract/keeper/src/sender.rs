@@ -0,0 +1,106 @@
+use crate::chain::Chain;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use multiversx_sc_snippets::{
+    imports::{tokio, Bech32Address},
+    log,
+    sdk::{data::transaction::Transaction, wallet::Wallet},
+};
+use std::time::Duration;
+
+/// Signs and sends the keeper's transactions, tracking the account nonce
+/// locally so several transactions can be in flight at once.
+pub struct Sender {
+    wallet: Wallet,
+    pub address: Bech32Address,
+    chain_id: String,
+    gas_price: u64,
+    tx_version: u32,
+    /// Next nonce to use; `None` until read from the network.
+    nonce: Option<u64>,
+    retries: u32,
+    retry_delay: Duration,
+}
+
+impl Sender {
+    pub async fn new(
+        chain: &Chain,
+        wallet: Wallet,
+        retries: u32,
+        retry_delay: Duration,
+    ) -> anyhow::Result<Self> {
+        let config = chain.network_config().await?;
+        Ok(Sender {
+            address: Bech32Address::from(wallet.to_address()),
+            wallet,
+            chain_id: config.chain_id,
+            gas_price: config.min_gas_price,
+            tx_version: config.min_transaction_version,
+            nonce: None,
+            retries,
+            retry_delay,
+        })
+    }
+
+    /// Sends a call to the contract, retrying with a growing delay. The nonce
+    /// only advances once the gateway accepts the transaction; if the gateway
+    /// says it is off, it is read again from the network.
+    pub async fn call(
+        &mut self,
+        chain: &Chain,
+        data: &str,
+        gas_limit: u64,
+    ) -> anyhow::Result<String> {
+        let mut attempt = 0;
+        loop {
+            let nonce = match self.nonce {
+                Some(nonce) => nonce,
+                None => chain.account_nonce(&self.address).await?,
+            };
+
+            let mut tx = Transaction {
+                nonce,
+                value: "0".to_owned(),
+                receiver: chain.contract.clone(),
+                sender: self.address.clone(),
+                gas_price: self.gas_price,
+                gas_limit,
+                data: Some(BASE64.encode(data)),
+                signature: None,
+                chain_id: self.chain_id.clone(),
+                version: self.tx_version,
+                options: 0,
+            };
+            tx.signature = Some(hex::encode(self.wallet.sign_tx(&tx)));
+
+            let err = match chain.send(&tx).await {
+                Ok(hash) => {
+                    self.nonce = Some(nonce + 1);
+                    return Ok(hash);
+                },
+                Err(err) => err,
+            };
+
+            if is_nonce_error(&err) {
+                self.nonce = None;
+            } else {
+                self.nonce = Some(nonce);
+            }
+            if attempt >= self.retries {
+                return Err(err.context(format!("giving up on `{data}` after {attempt} retries")));
+            }
+            attempt += 1;
+            log::warn!("sending `{data}` failed ({err:#}), retry {attempt}/{}", self.retries);
+            tokio::time::sleep(self.retry_delay * 2u32.pow(attempt - 1)).await;
+        }
+    }
+
+    /// Forgets the local nonce, e.g. after transactions were dropped.
+    pub fn resync_nonce(&mut self) {
+        self.nonce = None;
+    }
+}
+
+fn is_nonce_error(err: &anyhow::Error) -> bool {
+    let message = err.to_string();
+    message.contains("lowerNonceInTx: true") || message.contains("veryHighNonceInTx: true")
+}
@@ -0,0 +1,119 @@
+use multiversx_sc_snippets::imports::*;
+use public_commitment_fund_client::{CommitmentStatus, REVEAL_WINDOW_SECONDS};
+use public_commitment_fund_interactor::{config::Config, ContractInteract, CreateOptions};
+use public_commitment_fund_keeper::{
+    chain::Chain, finalizable_after, may_become_due, Keeper, KeeperConfig,
+};
+use std::time::Duration;
+
+const ONE_EGLD: u128 = 1_000_000_000_000_000_000;
+
+#[test]
+fn finalizable_after_matches_contract_rules() {
+    assert_eq!(finalizable_after(CommitmentStatus::Active, 100), Some(101));
+    assert_eq!(finalizable_after(CommitmentStatus::Completed, 100), Some(101));
+    assert_eq!(
+        finalizable_after(CommitmentStatus::Sealed, 100),
+        Some(100 + REVEAL_WINDOW_SECONDS + 1)
+    );
+    for status in [
+        CommitmentStatus::Failed,
+        CommitmentStatus::Refunded,
+        CommitmentStatus::Claimed,
+        CommitmentStatus::Swept,
+        CommitmentStatus::Forgiven,
+        CommitmentStatus::Verifying,
+    ] {
+        assert_eq!(finalizable_after(status, 100), None);
+    }
+}
+
+#[test]
+fn only_settled_commitments_are_skipped_for_good() {
+    for status in [
+        CommitmentStatus::Active,
+        CommitmentStatus::Completed,
+        CommitmentStatus::Sealed,
+        CommitmentStatus::Verifying,
+    ] {
+        assert!(may_become_due(status));
+    }
+    for status in [
+        CommitmentStatus::Failed,
+        CommitmentStatus::Refunded,
+        CommitmentStatus::Claimed,
+        CommitmentStatus::Swept,
+        CommitmentStatus::Forgiven,
+        CommitmentStatus::Releasing,
+    ] {
+        assert!(!may_become_due(status));
+    }
+}
+
+fn keeper_config(contract: &Bech32Address, wallet: Option<Wallet>) -> KeeperConfig {
+    KeeperConfig {
+        gateway_uri: Config::chain_simulator_config().gateway_uri,
+        contract: contract.clone(),
+        dry_run: wallet.is_none(),
+        wallet,
+        chain_simulator: true,
+        retries: 2,
+        retry_delay: Duration::from_secs(1),
+    }
+}
+
+// Needs a chain simulator listening on localhost:8085 and the contract built
+// (`cargo run -- build` in contract/meta). Run with
+// `cargo test --features chain-simulator-tests`.
+#[tokio::test]
+#[cfg_attr(not(feature = "chain-simulator-tests"), ignore)]
+async fn keeper_finalizes_expired_commitments_cs() {
+    let config = Config::chain_simulator_config();
+    let mut interact = ContractInteract::new(config.clone()).await;
+    let contract = interact.deploy().await;
+    let chain = Chain::new(&config.gateway_uri, contract.clone());
+
+    let recipient = Bech32Address::from(test_wallets::bob().to_address());
+    let deadline = chain.now().await.unwrap() + 400;
+    for deadline in [deadline, deadline, deadline + 100_000] {
+        interact
+            .create_commitment(
+                "Ship weekly report",
+                &recipient,
+                deadline,
+                ONE_EGLD,
+                &CreateOptions::default(),
+            )
+            .await;
+    }
+
+    let mut dry_run = Keeper::new(keeper_config(&contract, None)).await.unwrap();
+    assert!(dry_run.due_ids().await.unwrap().is_empty());
+
+    while chain.now().await.unwrap() <= deadline {
+        chain.generate_blocks(20).await.unwrap();
+    }
+    assert_eq!(dry_run.due_ids().await.unwrap(), vec![1, 2]);
+
+    let mut keeper = Keeper::new(keeper_config(&contract, Some(test_wallets::alice())))
+        .await
+        .unwrap();
+    let report = keeper.run_once().await.unwrap();
+    assert_eq!(report.due, vec![1, 2]);
+    assert_eq!(report.finalized, vec![1, 2]);
+    assert!(report.failed.is_empty());
+
+    let statuses: Vec<_> = interact
+        .get_commitments_batch(vec![1, 2, 3])
+        .await
+        .into_iter()
+        .map(|commitment| commitment.status)
+        .collect();
+    assert_eq!(
+        statuses,
+        vec![CommitmentStatus::Failed, CommitmentStatus::Failed, CommitmentStatus::Active]
+    );
+    assert!(keeper.run_once().await.unwrap().due.is_empty());
+    // The next scans start at the still active third commitment.
+    assert_eq!(keeper.low_water(), 2);
+}
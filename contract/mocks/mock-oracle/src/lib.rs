@@ -0,0 +1,24 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+/// Test stand-in for an attestation oracle: answers `verify` with whatever
+/// verdict was configured for the commitment id, `false` if none was.
+#[multiversx_sc::contract]
+pub trait MockOracle {
+    #[init]
+    fn init(&self) {}
+
+    #[endpoint(set_verdict)]
+    fn set_verdict(&self, id: u64, verdict: bool) {
+        self.verdicts(id).set(verdict);
+    }
+
+    #[endpoint(verify)]
+    fn verify(&self, id: u64, _creator: ManagedAddress) -> bool {
+        self.verdicts(id).get()
+    }
+
+    #[storage_mapper("verdicts")]
+    fn verdicts(&self, id: u64) -> SingleValueMapper<bool>;
+}
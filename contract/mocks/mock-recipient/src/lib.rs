@@ -0,0 +1,29 @@
+#![no_std]
+
+multiversx_sc::imports!();
+
+/// Test stand-in for a contract recipient: takes released stakes on
+/// `on_release`, recording what each commitment paid, unless told to reject them.
+#[multiversx_sc::contract]
+pub trait MockRecipient {
+    #[init]
+    fn init(&self) {}
+
+    #[endpoint(set_rejecting)]
+    fn set_rejecting(&self, rejecting: bool) {
+        self.rejecting().set(rejecting);
+    }
+
+    #[payable("EGLD")]
+    #[endpoint(on_release)]
+    fn on_release(&self, id: u64) {
+        require!(!self.rejecting().get(), "Payment rejected");
+        self.received(id).set(&*self.call_value().egld());
+    }
+
+    #[storage_mapper("rejecting")]
+    fn rejecting(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("received")]
+    fn received(&self, id: u64) -> SingleValueMapper<BigUint>;
+}
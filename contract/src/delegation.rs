@@ -0,0 +1,85 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::events::{self, DelegationEventData, EVENT_VERSION};
+
+pub const MAX_DELEGATES: usize = 10;
+
+/// What a delegate may do on behalf of the address that appointed it. A
+/// delegation covers every commitment of that address, present and future.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DelegationKind {
+    /// Call `claim` for commitments the owner is the recipient of; the stake
+    /// is still paid to the recipient.
+    Claim,
+    /// Submit proofs for commitments the owner created, through `submit_proof`,
+    /// `submit_typed_proof` or `submit_attested_proof`.
+    Proof,
+}
+
+#[multiversx_sc::module]
+pub trait DelegationModule: events::EventsModule {
+    #[endpoint(add_delegate)]
+    fn add_delegate(&self, kind: DelegationKind, delegate: ManagedAddress) {
+        let owner = self.blockchain().get_caller();
+        require!(delegate != owner, "Cannot delegate to self");
+        require!(!delegate.is_zero(), "Invalid delegate");
+
+        let mut delegates = self.delegates(&owner, kind);
+        require!(delegates.len() < MAX_DELEGATES, "Too many delegates");
+        require!(delegates.insert(delegate.clone()), "Already a delegate");
+
+        self.delegate_added_event(&owner, kind, &delegate, self.delegation_event_data());
+    }
+
+    #[endpoint(remove_delegate)]
+    fn remove_delegate(&self, kind: DelegationKind, delegate: ManagedAddress) {
+        let owner = self.blockchain().get_caller();
+        require!(
+            self.delegates(&owner, kind).swap_remove(&delegate),
+            "Not a delegate"
+        );
+
+        self.delegate_removed_event(&owner, kind, &delegate, self.delegation_event_data());
+    }
+
+    #[view(get_delegates)]
+    fn get_delegates(
+        &self,
+        owner: ManagedAddress,
+        kind: DelegationKind,
+    ) -> MultiValueEncoded<ManagedAddress> {
+        self.delegates(&owner, kind).iter().collect()
+    }
+
+    #[view(is_delegate)]
+    fn is_delegate(
+        &self,
+        owner: ManagedAddress,
+        kind: DelegationKind,
+        delegate: ManagedAddress,
+    ) -> bool {
+        self.delegates(&owner, kind).contains(&delegate)
+    }
+
+    /// Whether the caller is `owner` or one of its `kind` delegates.
+    fn caller_acts_for(&self, owner: &ManagedAddress, kind: DelegationKind) -> bool {
+        let caller = self.blockchain().get_caller();
+        caller == *owner || self.delegates(owner, kind).contains(&caller)
+    }
+
+    fn delegation_event_data(&self) -> DelegationEventData {
+        DelegationEventData {
+            version: EVENT_VERSION,
+            timestamp: self.blockchain().get_block_timestamp_seconds().as_u64_seconds(),
+        }
+    }
+
+    #[storage_mapper("delegates")]
+    fn delegates(
+        &self,
+        owner: &ManagedAddress,
+        kind: DelegationKind,
+    ) -> UnorderedSetMapper<ManagedAddress>;
+}
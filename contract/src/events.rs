@@ -0,0 +1,240 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+use crate::{delegation::DelegationKind, Commitment, CommitmentStatus, ProofKind};
+
+/// Schema version carried by every event. Bump it whenever an event's topics or
+/// data change shape, so consumers can decode old and new logs side by side.
+pub const EVENT_VERSION: u8 = 1;
+
+/// Data attached to every state transition event. Together with the event's
+/// topics it is enough to replay the transition without querying the contract.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct EventData<M: ManagedTypeApi> {
+    pub version: u8,
+    pub caller: ManagedAddress<M>,
+    pub timestamp: u64,
+    /// Status of the commitment after the transition.
+    pub status: CommitmentStatus,
+}
+
+/// Data of `CommitmentCreated`: the full record as stored at creation.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct CreatedEventData<M: ManagedTypeApi> {
+    pub version: u8,
+    pub commitment: Commitment<M>,
+}
+
+/// Data of `DelegateAdded` and `DelegateRemoved`, which change no commitment.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct DelegationEventData {
+    pub version: u8,
+    pub timestamp: u64,
+}
+
+#[multiversx_sc::module]
+pub trait EventsModule {
+    fn event_data(&self, status: CommitmentStatus) -> EventData<Self::Api> {
+        EventData {
+            version: EVENT_VERSION,
+            caller: self.blockchain().get_caller(),
+            timestamp: self.blockchain().get_block_timestamp_seconds().as_u64_seconds(),
+            status,
+        }
+    }
+
+    fn emit_commitment_created(&self, commitment: &Commitment<Self::Api>) {
+        self.commitment_created_event(
+            commitment.id,
+            &commitment.creator,
+            &commitment.recipient,
+            &commitment.amount,
+            commitment.deadline,
+            commitment.cooldown_seconds,
+            CreatedEventData {
+                version: EVENT_VERSION,
+                commitment: commitment.clone(),
+            },
+        );
+    }
+
+    #[event("CommitmentCreated")]
+    fn commitment_created_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] creator: &ManagedAddress,
+        #[indexed] recipient: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        #[indexed] deadline: u64,
+        #[indexed] cooldown: u64,
+        data: CreatedEventData<Self::Api>,
+    );
+
+    #[event("ProofSubmitted")]
+    fn proof_submitted_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] kind: ProofKind,
+        #[indexed] reference: &ManagedBuffer,
+        #[indexed] proof_hash: &ManagedBuffer,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("ProofRevised")]
+    fn proof_revised_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] revision: u64,
+        #[indexed] kind: ProofKind,
+        #[indexed] reference: &ManagedBuffer,
+        #[indexed] proof_hash: &ManagedBuffer,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("OracleVerificationRequested")]
+    fn oracle_verification_requested_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] oracle: &ManagedAddress,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("OracleVerified")]
+    fn oracle_verified_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] verified: bool,
+        data: EventData<Self::Api>,
+    );
+
+    /// The oracle call itself failed; the commitment is back to `Active`.
+    #[event("OracleVerificationFailed")]
+    fn oracle_verification_failed_event(&self, #[indexed] id: u64, data: EventData<Self::Api>);
+
+    #[event("ProofSealed")]
+    fn proof_sealed_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] proof_hash: &ManagedBuffer,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("ProofRevealed")]
+    fn proof_revealed_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] proof_url: &ManagedBuffer,
+        #[indexed] proof_hash: &ManagedBuffer,
+        data: EventData<Self::Api>,
+    );
+
+    /// `finalized_at` is the time the failure is recorded at. It is the block
+    /// time, except when `release_to_recipient` settles a lapsed commitment,
+    /// which is dated to when it lapsed.
+    #[event("FailedFinalized")]
+    fn failed_finalized_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] finalized_at: u64,
+        data: EventData<Self::Api>,
+    );
+
+    /// The creator gave up early; otherwise the same as `FailedFinalized`.
+    #[event("Conceded")]
+    fn conceded_event(&self, #[indexed] id: u64, data: EventData<Self::Api>);
+
+    #[event("Refunded")]
+    fn refunded_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] creator: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("Claimed")]
+    fn claimed_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] recipient: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        data: EventData<Self::Api>,
+    );
+
+    /// A contract recipient is being paid asynchronously; `Claimed` or
+    /// `ReleaseFailed` follows once the call returns.
+    #[event("ReleaseRequested")]
+    fn release_requested_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] recipient: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("ReleaseFailed")]
+    fn release_failed_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] recipient: &ManagedAddress,
+        #[indexed] reason: &ManagedBuffer,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("Swept")]
+    fn swept_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] fallback: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("Forgiven")]
+    fn forgiven_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] creator: &ManagedAddress,
+        #[indexed] amount: &BigUint,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("Cancelled")]
+    fn cancelled_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] refund: &BigUint,
+        #[indexed] penalty: &BigUint,
+        data: EventData<Self::Api>,
+    );
+
+    /// The full record was dropped; `record_hash` is kept in its `ArchivedCommitment`.
+    #[event("CommitmentPruned")]
+    fn commitment_pruned_event(
+        &self,
+        #[indexed] id: u64,
+        #[indexed] record_hash: &ManagedBuffer,
+        data: EventData<Self::Api>,
+    );
+
+    #[event("DelegateAdded")]
+    fn delegate_added_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] kind: DelegationKind,
+        #[indexed] delegate: &ManagedAddress,
+        data: DelegationEventData,
+    );
+
+    #[event("DelegateRemoved")]
+    fn delegate_removed_event(
+        &self,
+        #[indexed] owner: &ManagedAddress,
+        #[indexed] kind: DelegationKind,
+        #[indexed] delegate: &ManagedAddress,
+        data: DelegationEventData,
+    );
+}
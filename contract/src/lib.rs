@@ -3,16 +3,67 @@
 multiversx_sc::imports!();
 multiversx_sc::derive_imports!();
 
-pub const STATUS_ACTIVE: u8 = 0;
-pub const STATUS_COMPLETED: u8 = 1;
-pub const STATUS_FAILED: u8 = 2;
-pub const STATUS_REFUNDED: u8 = 3;
-pub const STATUS_CLAIMED: u8 = 4;
+pub mod delegation;
+pub mod events;
+pub mod proof;
+pub mod tags;
+
+pub use delegation::DelegationKind;
+pub use events::{CreatedEventData, DelegationEventData, EventData, EVENT_VERSION};
+pub use proof::ProofKind;
+
+#[type_abi]
+#[derive(
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    ManagedVecItem,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Debug,
+)]
+pub enum CommitmentStatus {
+    Active,
+    Completed,
+    Failed,
+    Refunded,
+    Claimed,
+    Swept,
+    Forgiven,
+    Sealed,
+    Verifying,
+    Releasing,
+}
+
+/// Layout version written alongside every commitment. Bump it, and keep a
+/// decoder for the old layout, whenever a deployed layout changes. Layouts 0
+/// and 1 kept the whole `Commitment` under `commitments(id)`; layout 2 splits
+/// it into terms, metadata, state and proof.
+///
+/// No intermediate shape of layout 1 was ever deployed. The fields added to
+/// `Commitment` after versioning, from sealed proofs to metadata, first shipped
+/// together, so layout 1 has a single decoder for its final shape.
+pub const STORAGE_VERSION: u8 = 2;
 
 const DEFAULT_COOLDOWN_SECONDS: u64 = 86_400;
+const DEFAULT_CLAIM_WINDOW_SECONDS: u64 = 365 * 86_400;
 const MIN_DEADLINE_BUFFER_SECONDS: u64 = 300;
 const MAX_TITLE_BYTES: usize = 64;
 const MAX_PROOF_URL_BYTES: usize = 512;
+const MAX_DESCRIPTION_BYTES: usize = 1_024;
+const MAX_METADATA_URI_BYTES: usize = 512;
+/// How long after the deadline a sealed proof can still be revealed.
+pub const REVEAL_WINDOW_SECONDS: u64 = 86_400;
+const MIN_SALT_BYTES: usize = 16;
+const ORACLE_VERIFY_ENDPOINT: &str = "verify";
+const MAX_RELEASE_ENDPOINT_BYTES: usize = 64;
+const DEFAULT_CANCEL_GRACE_SECONDS: u64 = 86_400;
+const DEFAULT_CANCEL_PENALTY_BPS: u64 = 1_000;
+const MAX_BPS: u64 = 10_000;
+const DEFAULT_ARCHIVE_RETENTION_SECONDS: u64 = 180 * 86_400;
 
 #[type_abi]
 #[derive(
@@ -33,21 +84,420 @@ pub struct Commitment<M: ManagedTypeApi> {
     pub deadline: u64,
     pub cooldown_seconds: u64,
     pub created_at: u64,
-    pub status: u8,
+    pub status: CommitmentStatus,
     pub title: ManagedBuffer<M>,
     pub proof_url: ManagedBuffer<M>,
     pub proof_hash: ManagedBuffer<M>,
     pub proof_submitted_at: u64,
     pub finalized_at: u64,
+    pub fallback: ManagedAddress<M>,
+    pub claim_window_seconds: u64,
+    pub forgiven_amount: BigUint<M>,
+    pub proof_kind: ProofKind,
+    pub oracle: ManagedAddress<M>,
+    pub attester: ManagedAddress<M>,
+    pub start_at: u64,
+    pub cancel_grace_seconds: u64,
+    pub cancel_penalty_bps: u64,
+    pub cancellable: bool,
+    pub tags: ManagedVec<M, ManagedBuffer<M>>,
+    pub description: ManagedBuffer<M>,
+    /// Off-chain JSON document with success criteria; empty when none.
+    pub metadata_uri: ManagedBuffer<M>,
+    /// sha256 of the document at `metadata_uri`; empty when none.
+    pub metadata_hash: ManagedBuffer<M>,
+}
+
+/// Optional settings of `create_commitment`. Fields left `None` take their
+/// defaults, so any of them can be set without the others.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct CreateOptions<M: ManagedTypeApi> {
+    pub cooldown_seconds: Option<u64>,
+    pub claim_window_seconds: Option<u64>,
+    /// Where an unclaimed stake is swept; the creator by default.
+    pub fallback: Option<ManagedAddress<M>>,
+    /// Contract asked to verify the goal instead of a submitted proof.
+    pub oracle: Option<ManagedAddress<M>>,
+    /// Key whose signature every proof must carry.
+    pub attester: Option<ManagedAddress<M>>,
+    /// Start of the proof window; the creation time by default.
+    pub start_at: Option<u64>,
+    pub cancel_grace_seconds: Option<u64>,
+    pub cancel_penalty_bps: Option<u64>,
+    pub cancellable: Option<bool>,
+    pub tags: Option<ManagedVec<M, ManagedBuffer<M>>>,
+    pub description: Option<ManagedBuffer<M>>,
+    /// Off-chain JSON document with success criteria; set together with `metadata_hash`.
+    pub metadata_uri: Option<ManagedBuffer<M>>,
+    pub metadata_hash: Option<ManagedByteArray<M, 32>>,
+}
+
+impl<M: ManagedTypeApi> Default for CreateOptions<M> {
+    fn default() -> Self {
+        CreateOptions {
+            cooldown_seconds: None,
+            claim_window_seconds: None,
+            fallback: None,
+            oracle: None,
+            attester: None,
+            start_at: None,
+            cancel_grace_seconds: None,
+            cancel_penalty_bps: None,
+            cancellable: None,
+            tags: None,
+            description: None,
+            metadata_uri: None,
+            metadata_hash: None,
+        }
+    }
+}
+
+/// Fields fixed at creation that endpoints check. Stored apart from the
+/// metadata so settling a commitment never reads its title or description.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, PartialEq, Debug)]
+pub struct CommitmentTerms<M: ManagedTypeApi> {
+    pub creator: ManagedAddress<M>,
+    pub recipient: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+    pub deadline: u64,
+    pub cooldown_seconds: u64,
+    pub created_at: u64,
+    pub fallback: ManagedAddress<M>,
+    pub claim_window_seconds: u64,
+    pub oracle: ManagedAddress<M>,
+    pub attester: ManagedAddress<M>,
+    pub start_at: u64,
+    pub cancel_grace_seconds: u64,
+    pub cancel_penalty_bps: u64,
+    pub cancellable: bool,
+}
+
+/// Fields fixed at creation that only views and events read.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct CommitmentMetadata<M: ManagedTypeApi> {
+    pub title: ManagedBuffer<M>,
+    pub tags: ManagedVec<M, ManagedBuffer<M>>,
+    pub description: ManagedBuffer<M>,
+    pub metadata_uri: ManagedBuffer<M>,
+    pub metadata_hash: ManagedBuffer<M>,
+}
+
+/// Everything that changes after creation, except the proof itself.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, PartialEq, Debug)]
+pub struct CommitmentState<M: ManagedTypeApi> {
+    pub status: CommitmentStatus,
+    pub proof_kind: ProofKind,
+    pub proof_submitted_at: u64,
+    pub finalized_at: u64,
+    pub forgiven_amount: BigUint<M>,
+}
+
+/// The latest proof of a commitment; only written when a proof is recorded.
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct CommitmentProof<M: ManagedTypeApi> {
+    pub proof_url: ManagedBuffer<M>,
+    pub proof_hash: ManagedBuffer<M>,
+}
+
+impl<M: ManagedTypeApi> Default for CommitmentProof<M> {
+    fn default() -> Self {
+        CommitmentProof {
+            proof_url: ManagedBuffer::new(),
+            proof_hash: ManagedBuffer::new(),
+        }
+    }
+}
+
+/// What endpoints load to act on a commitment: its terms and state, without
+/// the metadata and proof they do not need.
+#[derive(ManagedVecItem)]
+pub struct StoredCommitment<M: ManagedTypeApi> {
+    pub id: u64,
+    pub terms: CommitmentTerms<M>,
+    pub state: CommitmentState<M>,
+}
+
+impl<M: ManagedTypeApi> Commitment<M> {
+    pub fn from_parts(
+        id: u64,
+        terms: CommitmentTerms<M>,
+        metadata: CommitmentMetadata<M>,
+        state: CommitmentState<M>,
+        proof: CommitmentProof<M>,
+    ) -> Self {
+        Commitment {
+            id,
+            creator: terms.creator,
+            recipient: terms.recipient,
+            amount: terms.amount,
+            deadline: terms.deadline,
+            cooldown_seconds: terms.cooldown_seconds,
+            created_at: terms.created_at,
+            status: state.status,
+            title: metadata.title,
+            proof_url: proof.proof_url,
+            proof_hash: proof.proof_hash,
+            proof_submitted_at: state.proof_submitted_at,
+            finalized_at: state.finalized_at,
+            fallback: terms.fallback,
+            claim_window_seconds: terms.claim_window_seconds,
+            forgiven_amount: state.forgiven_amount,
+            proof_kind: state.proof_kind,
+            oracle: terms.oracle,
+            attester: terms.attester,
+            start_at: terms.start_at,
+            cancel_grace_seconds: terms.cancel_grace_seconds,
+            cancel_penalty_bps: terms.cancel_penalty_bps,
+            cancellable: terms.cancellable,
+            tags: metadata.tags,
+            description: metadata.description,
+            metadata_uri: metadata.metadata_uri,
+            metadata_hash: metadata.metadata_hash,
+        }
+    }
+
+    pub fn terms(&self) -> CommitmentTerms<M> {
+        CommitmentTerms {
+            creator: self.creator.clone(),
+            recipient: self.recipient.clone(),
+            amount: self.amount.clone(),
+            deadline: self.deadline,
+            cooldown_seconds: self.cooldown_seconds,
+            created_at: self.created_at,
+            fallback: self.fallback.clone(),
+            claim_window_seconds: self.claim_window_seconds,
+            oracle: self.oracle.clone(),
+            attester: self.attester.clone(),
+            start_at: self.start_at,
+            cancel_grace_seconds: self.cancel_grace_seconds,
+            cancel_penalty_bps: self.cancel_penalty_bps,
+            cancellable: self.cancellable,
+        }
+    }
+
+    pub fn metadata(&self) -> CommitmentMetadata<M> {
+        CommitmentMetadata {
+            title: self.title.clone(),
+            tags: self.tags.clone(),
+            description: self.description.clone(),
+            metadata_uri: self.metadata_uri.clone(),
+            metadata_hash: self.metadata_hash.clone(),
+        }
+    }
+
+    pub fn state(&self) -> CommitmentState<M> {
+        CommitmentState {
+            status: self.status,
+            proof_kind: self.proof_kind,
+            proof_submitted_at: self.proof_submitted_at,
+            finalized_at: self.finalized_at,
+            forgiven_amount: self.forgiven_amount.clone(),
+        }
+    }
+
+    pub fn proof(&self) -> CommitmentProof<M> {
+        CommitmentProof {
+            proof_url: self.proof_url.clone(),
+            proof_hash: self.proof_hash.clone(),
+        }
+    }
+}
+
+/// One submitted proof; `proof_history(id)` keeps every revision in order.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, ManagedVecItem, Clone, PartialEq, Debug)]
+pub struct ProofEntry<M: ManagedTypeApi> {
+    pub kind: ProofKind,
+    pub reference: ManagedBuffer<M>,
+    pub proof_hash: ManagedBuffer<M>,
+    pub submitted_at: u64,
+}
+
+/// What `prune_commitments` keeps of a settled commitment. `record_hash` is the
+/// sha256 of the top-encoded `Commitment` that `get_commitment` returned just
+/// before pruning, rebuilt from its terms, metadata, state and proof entries,
+/// so an off-chain copy can be checked against it.
+#[type_abi]
+#[derive(TopEncode, TopDecode, NestedEncode, NestedDecode, Clone, PartialEq, Debug)]
+pub struct ArchivedCommitment<M: ManagedTypeApi> {
+    pub id: u64,
+    pub creator: ManagedAddress<M>,
+    pub recipient: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+    pub status: CommitmentStatus,
+    pub settled_at: u64,
+    pub record_hash: ManagedBuffer<M>,
+}
+
+/// `Commitment` as laid out by the first deployment, before per-record versions
+/// were tracked. Kept only so those records keep decoding.
+#[derive(TopEncode, TopDecode)]
+pub struct CommitmentV0<M: ManagedTypeApi> {
+    pub id: u64,
+    pub creator: ManagedAddress<M>,
+    pub recipient: ManagedAddress<M>,
+    pub amount: BigUint<M>,
+    pub deadline: u64,
+    pub cooldown_seconds: u64,
+    pub created_at: u64,
+    pub status: CommitmentStatus,
+    pub title: ManagedBuffer<M>,
+    pub proof_url: ManagedBuffer<M>,
+    pub proof_hash: ManagedBuffer<M>,
+    pub proof_submitted_at: u64,
+    pub finalized_at: u64,
+}
+
+impl<M: ManagedTypeApi> CommitmentV0<M> {
+    pub fn into_current(self) -> Commitment<M> {
+        Commitment {
+            id: self.id,
+            fallback: self.creator.clone(),
+            creator: self.creator,
+            recipient: self.recipient,
+            amount: self.amount,
+            deadline: self.deadline,
+            cooldown_seconds: self.cooldown_seconds,
+            created_at: self.created_at,
+            status: self.status,
+            title: self.title,
+            proof_url: self.proof_url,
+            proof_hash: self.proof_hash,
+            proof_submitted_at: self.proof_submitted_at,
+            finalized_at: self.finalized_at,
+            // Their recipients could always claim; nothing may sweep them.
+            claim_window_seconds: u64::MAX,
+            forgiven_amount: BigUint::zero(),
+            proof_kind: ProofKind::HttpUrl,
+            oracle: ManagedAddress::zero(),
+            attester: ManagedAddress::zero(),
+            start_at: self.created_at,
+            // These were created when cancelling was always free.
+            cancel_grace_seconds: self.deadline - self.created_at,
+            cancel_penalty_bps: 0,
+            cancellable: true,
+            tags: ManagedVec::new(),
+            description: ManagedBuffer::new(),
+            metadata_uri: ManagedBuffer::new(),
+            metadata_hash: ManagedBuffer::new(),
+        }
+    }
 }
 
 #[multiversx_sc::contract]
-pub trait PublicCommitmentFund {
+pub trait PublicCommitmentFund: events::EventsModule + delegation::DelegationModule {
     #[init]
     fn init(&self) {
         self.next_id().set(1u64);
     }
 
+    #[upgrade]
+    fn upgrade(&self) {}
+
+    /// Re-encodes commitments still stored in an older layout and indexes the
+    /// ones that can still pay out. Ids already on the current layout are skipped.
+    #[only_owner]
+    #[endpoint(migrate_commitments)]
+    fn migrate_commitments(&self, ids: MultiValueEncoded<u64>) {
+        for id in ids.into_iter() {
+            if self.commitment_version(id).get() == STORAGE_VERSION
+                || self.commitments(id).is_empty()
+            {
+                continue;
+            }
+
+            self.migrate_commitment(id);
+        }
+    }
+
+    /// How long a settled commitment is kept in full before it may be pruned.
+    #[only_owner]
+    #[endpoint(set_archive_retention_seconds)]
+    fn set_archive_retention_seconds(&self, seconds: u64) {
+        self.archive_retention_seconds().set(seconds);
+    }
+
+    /// Replaces settled commitments (refunded, claimed, swept or forgiven) whose
+    /// retention period has passed with an `ArchivedCommitment`, dropping their
+    /// proof history and tag index entries. Callable by anyone; ids that are not
+    /// prunable yet are skipped.
+    #[endpoint(prune_commitments)]
+    fn prune_commitments(&self, ids: MultiValueEncoded<u64>) {
+        let now = self.now();
+        let retention = self.get_archive_retention_seconds();
+
+        for id in ids.into_iter() {
+            if !self.commitment_exists(id) {
+                continue;
+            }
+            let commitment = self.load_commitment(id);
+            if !self.is_settled(commitment.status) {
+                continue;
+            }
+            let settled_at = self.settled_time(id, commitment.finalized_at);
+            if now < settled_at.saturating_add(retention) {
+                continue;
+            }
+
+            for tag in commitment.tags.iter() {
+                self.ids_by_tag(&tag).swap_remove(&id);
+            }
+
+            let encoded = self.serializer().top_encode_to_managed_buffer(&commitment);
+            let archived = ArchivedCommitment {
+                id,
+                creator: commitment.creator,
+                recipient: commitment.recipient,
+                amount: commitment.amount,
+                status: commitment.status,
+                settled_at,
+                record_hash: self.crypto().sha256(&encoded).as_managed_buffer().clone(),
+            };
+
+            self.commitments(id).clear();
+            self.commitment_version(id).clear();
+            self.commitment_terms(id).clear();
+            self.commitment_metadata(id).clear();
+            self.commitment_state(id).clear();
+            self.commitment_proof(id).clear();
+            self.proof_history(id).clear();
+            self.settled_at(id).clear();
+            self.archived_commitments(id).set(&archived);
+
+            self.commitment_pruned_event(
+                id,
+                &archived.record_hash,
+                self.event_data(archived.status),
+            );
+        }
+    }
+
+    /// Restricts `create_commitment` tags to the owner-curated vocabulary when
+    /// enabled; otherwise any well-formed tag is accepted.
+    #[only_owner]
+    #[endpoint(set_curated_tags_only)]
+    fn set_curated_tags_only(&self, curated: bool) {
+        self.curated_tags_only().set(curated);
+    }
+
+    #[only_owner]
+    #[endpoint(add_allowed_tags)]
+    fn add_allowed_tags(&self, tags: MultiValueEncoded<ManagedBuffer>) {
+        for tag in tags.into_iter() {
+            self.require_valid_tag(&tag);
+            self.allowed_tags().insert(tag);
+        }
+    }
+
+    #[only_owner]
+    #[endpoint(remove_allowed_tags)]
+    fn remove_allowed_tags(&self, tags: MultiValueEncoded<ManagedBuffer>) {
+        for tag in tags.into_iter() {
+            self.allowed_tags().swap_remove(&tag);
+        }
+    }
+
     #[payable("EGLD")]
     #[endpoint(create_commitment)]
     fn create_commitment(
@@ -55,7 +505,7 @@ pub trait PublicCommitmentFund {
         title: ManagedBuffer,
         recipient: ManagedAddress,
         deadline: u64,
-        cooldown_seconds_opt: OptionalValue<u64>,
+        options_opt: OptionalValue<CreateOptions<Self::Api>>,
     ) {
         let payment = self.call_value().egld();
         require!(*payment > 0u64, "Amount must be > 0");
@@ -68,135 +518,692 @@ pub trait PublicCommitmentFund {
             "Deadline too soon"
         );
 
-        let cooldown_seconds = match cooldown_seconds_opt {
-            OptionalValue::Some(value) => value,
-            OptionalValue::None => DEFAULT_COOLDOWN_SECONDS,
+        let options = match options_opt {
+            OptionalValue::Some(options) => options,
+            OptionalValue::None => CreateOptions::default(),
+        };
+
+        let start_at = match options.start_at {
+            Some(value) => {
+                require!(value >= now, "Start time in the past");
+                // Checked, as a start time near `u64::MAX` would wrap around.
+                require!(
+                    value
+                        .checked_add(MIN_DEADLINE_BUFFER_SECONDS)
+                        .is_some_and(|earliest| deadline > earliest),
+                    "Start time too close to deadline"
+                );
+                value
+            },
+            None => now,
         };
+
+        let cooldown_seconds = options.cooldown_seconds.unwrap_or(DEFAULT_COOLDOWN_SECONDS);
         require!(cooldown_seconds > 0, "Cooldown must be > 0");
 
-        let id = self.next_id().get();
-        self.next_id().set(id + 1);
+        let claim_window_seconds = options
+            .claim_window_seconds
+            .unwrap_or(DEFAULT_CLAIM_WINDOW_SECONDS);
+        require!(claim_window_seconds > 0, "Claim window must be > 0");
 
         let creator = self.blockchain().get_caller();
-        let amount = payment.clone_value();
+        let fallback = options.fallback.unwrap_or_else(|| creator.clone());
+        require!(!fallback.is_zero(), "Fallback cannot be zero address");
+
+        let oracle = match options.oracle {
+            Some(address) => {
+                require!(
+                    self.blockchain().is_smart_contract(&address),
+                    "Oracle must be a smart contract"
+                );
+                address
+            },
+            None => ManagedAddress::zero(),
+        };
+
+        let attester = match options.attester {
+            Some(address) => {
+                require!(!address.is_zero(), "Attester cannot be zero address");
+                address
+            },
+            None => ManagedAddress::zero(),
+        };
+
+        let cancel_grace_seconds = options
+            .cancel_grace_seconds
+            .unwrap_or(DEFAULT_CANCEL_GRACE_SECONDS);
+
+        let cancel_penalty_bps = options.cancel_penalty_bps.unwrap_or(DEFAULT_CANCEL_PENALTY_BPS);
+        require!(cancel_penalty_bps <= MAX_BPS, "Cancel penalty exceeds 100%");
+
+        let cancellable = options.cancellable.unwrap_or(true);
+
+        let tags = options.tags.unwrap_or_default();
+        self.require_valid_commitment_tags(&tags);
+
+        let description = options.description.unwrap_or_default();
+        require!(description.len() <= MAX_DESCRIPTION_BYTES, "Description too long");
+
+        let (metadata_uri, metadata_hash) = match (options.metadata_uri, options.metadata_hash) {
+            (Some(uri), Some(hash)) => {
+                require!(
+                    !uri.is_empty() && uri.len() <= MAX_METADATA_URI_BYTES,
+                    "Invalid metadata URI length"
+                );
+                let mut uri_bytes = [0u8; MAX_METADATA_URI_BYTES];
+                require!(
+                    proof::is_metadata_uri(uri.load_to_byte_array(&mut uri_bytes)),
+                    "Invalid metadata URI"
+                );
+                (uri, hash.as_managed_buffer().clone())
+            },
+            (None, None) => (ManagedBuffer::new(), ManagedBuffer::new()),
+            _ => sc_panic!("Metadata URI and hash go together"),
+        };
+
+        let id = self.next_id().get();
+        self.next_id().set(id + 1);
 
         let commitment = Commitment {
             id,
-            creator: creator.clone(),
-            recipient: recipient.clone(),
-            amount: amount.clone(),
+            creator,
+            recipient,
+            amount: payment.clone_value(),
             deadline,
             cooldown_seconds,
             created_at: now,
-            status: STATUS_ACTIVE,
+            status: CommitmentStatus::Active,
             title,
             proof_url: ManagedBuffer::new(),
             proof_hash: ManagedBuffer::new(),
             proof_submitted_at: 0,
             finalized_at: 0,
+            fallback,
+            claim_window_seconds,
+            forgiven_amount: BigUint::zero(),
+            proof_kind: ProofKind::HttpUrl,
+            oracle,
+            attester,
+            start_at,
+            cancel_grace_seconds,
+            cancel_penalty_bps,
+            cancellable,
+            tags,
+            description,
+            metadata_uri,
+            metadata_hash,
         };
 
-        self.commitments(id).set(commitment);
-        self.all_ids().push(&id);
+        self.store_commitment(&commitment);
+        self.open_ids_by_recipient(&commitment.recipient).insert(id);
+        for tag in commitment.tags.iter() {
+            self.ids_by_tag(&tag).insert(id);
+        }
 
-        self.commitment_created_event(id, creator, recipient, amount, deadline, cooldown_seconds);
+        self.emit_commitment_created(&commitment);
     }
 
     #[endpoint(submit_proof)]
     fn submit_proof(&self, id: u64, proof_url: ManagedBuffer) {
-        let mut commitment = self.get_commitment_or_fail(id);
-
-        let caller = self.blockchain().get_caller();
-        require!(caller == commitment.creator, "Only creator can submit proof");
-        require!(commitment.status == STATUS_ACTIVE, "Commitment is not active");
+        let mut commitment = self.get_stored_or_fail(id);
+        self.require_can_submit_proof(&commitment);
+        self.require_self_reported(&commitment);
         require!(
-            proof_url.len() > 0 && proof_url.len() <= MAX_PROOF_URL_BYTES,
+            !proof_url.is_empty() && proof_url.len() <= MAX_PROOF_URL_BYTES,
             "Invalid proof URL length"
         );
 
-        let now = self.now();
-        require!(now <= commitment.deadline, "Deadline passed");
+        let hash = self.crypto().sha256(&proof_url);
+        let proof_hash = hash.as_managed_buffer().clone();
+        self.record_proof(&mut commitment, ProofKind::HttpUrl, proof_url, proof_hash);
+    }
+
+    /// Submits a typed proof. `reference` is validated against `kind`, and
+    /// `evidence_hash` is the creator's own hash of the evidence it points at.
+    #[endpoint(submit_typed_proof)]
+    fn submit_typed_proof(
+        &self,
+        id: u64,
+        kind: ProofKind,
+        reference: ManagedBuffer,
+        evidence_hash: ManagedByteArray<32>,
+    ) {
+        let mut commitment = self.get_stored_or_fail(id);
+        self.require_can_submit_proof(&commitment);
+        self.require_self_reported(&commitment);
+        require!(
+            !reference.is_empty() && reference.len() <= MAX_PROOF_URL_BYTES,
+            "Invalid proof reference length"
+        );
+
+        let mut reference_bytes = [0u8; MAX_PROOF_URL_BYTES];
+        let reference_slice = reference.load_to_byte_array(&mut reference_bytes);
+        require!(
+            kind.is_valid_reference(reference_slice),
+            "Invalid proof reference"
+        );
+
+        let proof_hash = evidence_hash.as_managed_buffer().clone();
+        self.record_proof(&mut commitment, kind, reference, proof_hash);
+    }
+
+    /// Submits a proof URL together with the attester's ed25519 signature over
+    /// `get_attestation_message(id, proof_url)`. Required when an attester is set.
+    #[endpoint(submit_attested_proof)]
+    fn submit_attested_proof(
+        &self,
+        id: u64,
+        proof_url: ManagedBuffer,
+        signature: ManagedByteArray<64>,
+    ) {
+        let mut commitment = self.get_stored_or_fail(id);
+        self.require_can_submit_proof(&commitment);
+        require!(!commitment.terms.attester.is_zero(), "No attester configured");
         require!(
-            commitment.proof_url.is_empty() && commitment.proof_submitted_at == 0,
-            "Proof already submitted"
+            !proof_url.is_empty() && proof_url.len() <= MAX_PROOF_URL_BYTES,
+            "Invalid proof URL length"
         );
 
         let hash = self.crypto().sha256(&proof_url);
         let proof_hash = hash.as_managed_buffer().clone();
+        let message = self.attestation_message(id, &proof_hash);
+        self.crypto().verify_ed25519(
+            commitment.terms.attester.as_managed_buffer(),
+            &message,
+            signature.as_managed_buffer(),
+        );
 
-        commitment.proof_url = proof_url;
-        commitment.proof_hash = proof_hash.clone();
-        commitment.proof_submitted_at = now;
-        commitment.status = STATUS_COMPLETED;
+        self.record_proof(&mut commitment, ProofKind::AttestedUrl, proof_url, proof_hash);
+    }
+
+    /// Asks the commitment's oracle contract whether the goal was met. The
+    /// callback completes the commitment on `true` and fails it on `false`.
+    #[endpoint(request_oracle_verification)]
+    fn request_oracle_verification(&self, id: u64) {
+        let mut commitment = self.get_stored_or_fail(id);
+
+        let caller = self.blockchain().get_caller();
+        require!(
+            caller == commitment.terms.creator,
+            "Only creator can request verification"
+        );
+        require!(!commitment.terms.oracle.is_zero(), "No oracle configured");
+        require!(
+            commitment.state.status == CommitmentStatus::Active,
+            "Commitment is not active"
+        );
+        self.require_proof_window_open(&commitment);
 
-        self.commitments(id).set(commitment);
-        self.proof_submitted_event(id, proof_hash);
+        commitment.state.status = CommitmentStatus::Verifying;
+        self.save_state(&commitment);
+        self.oracle_verification_requested_event(
+            id,
+            &commitment.terms.oracle,
+            self.event_data(commitment.state.status),
+        );
+
+        self.tx()
+            .to(&commitment.terms.oracle)
+            .raw_call(ORACLE_VERIFY_ENDPOINT)
+            .argument(&id)
+            .argument(&commitment.terms.creator)
+            .callback(self.callbacks().oracle_verification_callback(id))
+            .async_call_and_exit();
+    }
+
+    #[callback]
+    fn oracle_verification_callback(
+        &self,
+        id: u64,
+        #[call_result] result: ManagedAsyncCallResult<bool>,
+    ) {
+        let mut commitment = self.load_stored(id);
+        if commitment.state.status != CommitmentStatus::Verifying {
+            return;
+        }
+
+        let now = self.now();
+        match result {
+            ManagedAsyncCallResult::Ok(true) => {
+                commitment.state.status = CommitmentStatus::Completed;
+                commitment.state.proof_kind = ProofKind::OracleAttestation;
+                commitment.state.proof_submitted_at = now;
+                let proof = CommitmentProof {
+                    proof_url: commitment.terms.oracle.as_managed_buffer().clone(),
+                    proof_hash: ManagedBuffer::new(),
+                };
+                self.save_state(&commitment);
+                self.commitment_proof(id).set(&proof);
+                self.push_proof_history(&commitment, &proof);
+                self.oracle_verified_event(id, true, self.event_data(commitment.state.status));
+            },
+            ManagedAsyncCallResult::Ok(false) => {
+                self.mark_failed(&mut commitment, now);
+                self.save_state(&commitment);
+                self.oracle_verified_event(id, false, self.event_data(commitment.state.status));
+            },
+            // The oracle call itself failed; the creator may ask again.
+            ManagedAsyncCallResult::Err(_) => {
+                commitment.state.status = CommitmentStatus::Active;
+                self.save_state(&commitment);
+                self.oracle_verification_failed_event(
+                    id,
+                    self.event_data(commitment.state.status),
+                );
+            },
+        }
+    }
+
+    /// Commits to a proof without publishing it: `proof_hash` is
+    /// `sha256(proof_url ++ salt)`, revealed with `reveal_proof` after the deadline.
+    #[endpoint(submit_sealed_proof)]
+    fn submit_sealed_proof(&self, id: u64, proof_hash: ManagedByteArray<32>) {
+        let mut commitment = self.get_stored_or_fail(id);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == commitment.terms.creator, "Only creator can submit proof");
+        require!(
+            commitment.state.status == CommitmentStatus::Active,
+            "Commitment is not active"
+        );
+        self.require_self_reported(&commitment);
+
+        self.require_proof_window_open(&commitment);
+
+        let proof = CommitmentProof {
+            proof_url: ManagedBuffer::new(),
+            proof_hash: proof_hash.as_managed_buffer().clone(),
+        };
+        commitment.state.proof_submitted_at = self.now();
+        commitment.state.status = CommitmentStatus::Sealed;
+
+        self.save_state(&commitment);
+        self.commitment_proof(id).set(&proof);
+        self.proof_sealed_event(id, &proof.proof_hash, self.event_data(commitment.state.status));
+    }
+
+    /// Publishes a sealed proof. Only accepted after the deadline and within the
+    /// reveal window; an unrevealed commitment fails once the window closes.
+    #[endpoint(reveal_proof)]
+    fn reveal_proof(&self, id: u64, proof_url: ManagedBuffer, salt: ManagedBuffer) {
+        let mut commitment = self.get_stored_or_fail(id);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == commitment.terms.creator, "Only creator can reveal proof");
+        require!(
+            commitment.state.status == CommitmentStatus::Sealed,
+            "Commitment is not sealed"
+        );
+        require!(
+            !proof_url.is_empty() && proof_url.len() <= MAX_PROOF_URL_BYTES,
+            "Invalid proof URL length"
+        );
+        require!(salt.len() >= MIN_SALT_BYTES, "Salt too short");
+
+        let now = self.now();
+        require!(now > commitment.terms.deadline, "Reveal window not open");
+        require!(now <= self.settle_after(&commitment), "Reveal window closed");
+
+        let mut proof = self.commitment_proof(id).get();
+        let mut preimage = proof_url.clone();
+        preimage.append(&salt);
+        let hash = self.crypto().sha256(&preimage);
+        require!(
+            hash.as_managed_buffer() == &proof.proof_hash,
+            "Proof does not match sealed hash"
+        );
+
+        proof.proof_url = proof_url;
+        commitment.state.status = CommitmentStatus::Completed;
+
+        self.save_state(&commitment);
+        self.commitment_proof(id).set(&proof);
+        self.push_proof_history(&commitment, &proof);
+        self.proof_revealed_event(
+            id,
+            &proof.proof_url,
+            &proof.proof_hash,
+            self.event_data(commitment.state.status),
+        );
     }
 
     #[endpoint(finalize)]
     fn finalize(&self, id: u64) {
-        let mut commitment = self.get_commitment_or_fail(id);
+        let mut commitment = self.get_stored_or_fail(id);
         let now = self.now();
 
-        require!(now > commitment.deadline, "Deadline not reached");
+        require!(now > commitment.terms.deadline, "Deadline not reached");
         require!(
-            commitment.status == STATUS_ACTIVE || commitment.status == STATUS_COMPLETED,
+            matches!(
+                commitment.state.status,
+                CommitmentStatus::Active | CommitmentStatus::Completed | CommitmentStatus::Sealed
+            ),
             "Commitment cannot be finalized"
         );
+        require!(now > self.settle_after(&commitment), "Reveal window open");
 
-        commitment.finalized_at = now;
+        commitment.state.finalized_at = now;
 
-        if commitment.status == STATUS_COMPLETED {
-            self.send().direct_egld(&commitment.creator, &commitment.amount);
-            commitment.status = STATUS_REFUNDED;
-            self.commitments(id).set(commitment);
-            self.refunded_event(id);
+        if commitment.state.status == CommitmentStatus::Completed {
+            let terms = &commitment.terms;
+            self.send().direct_egld(&terms.creator, &terms.amount);
+            commitment.state.status = CommitmentStatus::Refunded;
+            self.open_ids_by_recipient(&terms.recipient).swap_remove(&id);
+            self.save_state(&commitment);
+            self.refunded_event(
+                id,
+                &terms.creator,
+                &terms.amount,
+                self.event_data(commitment.state.status),
+            );
             return;
         }
 
-        commitment.status = STATUS_FAILED;
-        self.commitments(id).set(commitment);
-        self.failed_finalized_event(id);
+        self.mark_failed(&mut commitment, now);
+        self.save_state(&commitment);
     }
 
     #[endpoint(claim)]
     fn claim(&self, id: u64) {
-        let mut commitment = self.get_commitment_or_fail(id);
+        let mut commitment = self.get_stored_or_fail(id);
+
+        require!(
+            self.caller_acts_for(&commitment.terms.recipient, DelegationKind::Claim),
+            "Only recipient can claim"
+        );
+
+        let now = self.now();
+        self.require_claimable(&commitment, now);
+
+        if commitment.state.status != CommitmentStatus::Failed {
+            self.mark_failed(&mut commitment, now);
+        }
+
+        self.send()
+            .direct_egld(&commitment.terms.recipient, &self.remaining_amount(&commitment));
+        self.mark_claimed(&mut commitment);
+    }
+
+    /// Claims the failed commitments of the caller whose cooldown has elapsed,
+    /// paying the sum out in a single transfer. Looks at `limit` of the
+    /// caller's open commitments, from position `start` (0 by default) of
+    /// `get_total_open_ids`, so commitments that are not claimable yet cannot
+    /// make the call run out of gas. Expired commitments nobody finalized are
+    /// settled along the way.
+    #[endpoint(claim_all)]
+    fn claim_all(&self, limit: u64, start_opt: OptionalValue<u64>) {
+        require!(limit > 0, "Limit must be > 0");
+
+        let caller = self.blockchain().get_caller();
+        let now = self.now();
+
+        let open_ids = self.open_ids_by_recipient(&caller);
+        let start = start_opt.into_option().unwrap_or_default();
+        let end_inclusive = core::cmp::min(open_ids.len() as u64, start.saturating_add(limit));
+        let mut claimable = ManagedVec::<Self::Api, StoredCommitment<Self::Api>>::new();
+        for index in start + 1..=end_inclusive {
+            let commitment = self.get_stored_or_fail(open_ids.get_by_index(index as usize));
+            if self.is_claimable(&commitment, now) {
+                claimable.push(commitment);
+            }
+        }
+        require!(!claimable.is_empty(), "Nothing to claim");
+
+        // Paid in a second pass, as `mark_claimed` reorders `open_ids`.
+        let mut total = BigUint::zero();
+        for mut commitment in claimable.into_iter() {
+            if commitment.state.status != CommitmentStatus::Failed {
+                self.mark_failed(&mut commitment, now);
+            }
+            total += self.remaining_amount(&commitment);
+            self.mark_claimed(&mut commitment);
+        }
+
+        self.send().direct_egld(&caller, &total);
+    }
+
+    /// Pays a claimable stake out to its recipient on their behalf. Callable by
+    /// anyone. Contract recipients are paid with an async call, to the endpoint
+    /// they registered with `set_release_endpoint` if any, so a rejected
+    /// payment leaves the stake claimable instead of failing the transaction.
+    #[endpoint(release_to_recipient)]
+    fn release_to_recipient(&self, id: u64) {
+        let mut commitment = self.get_stored_or_fail(id);
+
+        let now = self.now();
+        self.require_claimable(&commitment, now);
+
+        // Dated to when it lapsed, so a rejected release does not restart the
+        // cooldown.
+        if commitment.state.status != CommitmentStatus::Failed {
+            let settled_at = self.settle_after(&commitment);
+            self.mark_failed(&mut commitment, settled_at);
+        }
+
+        let amount = self.remaining_amount(&commitment);
+        let recipient = commitment.terms.recipient.clone();
+        if !self.blockchain().is_smart_contract(&recipient) {
+            self.send().direct_egld(&recipient, &amount);
+            self.mark_claimed(&mut commitment);
+            return;
+        }
+
+        commitment.state.status = CommitmentStatus::Releasing;
+        self.save_state(&commitment);
+        self.release_requested_event(
+            id,
+            &recipient,
+            &amount,
+            self.event_data(commitment.state.status),
+        );
+
+        let endpoint = self.release_endpoint(&recipient).get();
+        let call = if endpoint.is_empty() {
+            FunctionCall::empty()
+        } else {
+            FunctionCall::new(endpoint).argument(&id)
+        };
+        self.tx()
+            .to(&recipient)
+            .egld(&amount)
+            .raw_data(call)
+            .callback(self.callbacks().release_callback(id))
+            .async_call_and_exit();
+    }
+
+    #[callback]
+    fn release_callback(&self, id: u64, #[call_result] result: ManagedAsyncCallResult<()>) {
+        let mut commitment = self.load_stored(id);
+        if commitment.state.status != CommitmentStatus::Releasing {
+            return;
+        }
+
+        match result {
+            ManagedAsyncCallResult::Ok(()) => self.mark_claimed(&mut commitment),
+            // The stake came back with the failed call; it stays claimable.
+            ManagedAsyncCallResult::Err(err) => {
+                commitment.state.status = CommitmentStatus::Failed;
+                self.save_state(&commitment);
+                self.release_failed_event(
+                    id,
+                    &commitment.terms.recipient,
+                    &err.err_msg,
+                    self.event_data(commitment.state.status),
+                );
+            },
+        }
+    }
 
+    /// Sets, or clears when omitted, the endpoint `release_to_recipient` calls
+    /// on the calling contract with the stake and the commitment id.
+    #[endpoint(set_release_endpoint)]
+    fn set_release_endpoint(&self, endpoint_opt: OptionalValue<ManagedBuffer>) {
         let caller = self.blockchain().get_caller();
-        require!(caller == commitment.recipient, "Only recipient can claim");
-        require!(commitment.status == STATUS_FAILED, "Commitment is not failed");
-        require!(commitment.finalized_at > 0, "Commitment not finalized");
+        require!(
+            self.blockchain().is_smart_contract(&caller),
+            "Only contracts can set a release endpoint"
+        );
+
+        match endpoint_opt {
+            OptionalValue::Some(endpoint) => {
+                require!(
+                    !endpoint.is_empty() && endpoint.len() <= MAX_RELEASE_ENDPOINT_BYTES,
+                    "Invalid endpoint name"
+                );
+                self.release_endpoint(&caller).set(endpoint);
+            },
+            OptionalValue::None => self.release_endpoint(&caller).clear(),
+        }
+    }
+
+    /// Sends a forfeited stake the recipient never claimed to the commitment's
+    /// fallback address once its claim window has expired. Callable by anyone.
+    #[endpoint(sweep)]
+    fn sweep(&self, id: u64) {
+        let mut commitment = self.get_stored_or_fail(id);
 
         let now = self.now();
-        let claim_time = commitment.finalized_at + commitment.cooldown_seconds;
-        require!(now >= claim_time, "Cooldown not reached");
+        require!(
+            commitment.state.status == CommitmentStatus::Failed
+                || self.is_lapsed(&commitment, now),
+            "Commitment is not failed"
+        );
+        require!(now >= self.claim_expiry(&commitment), "Claim window not expired");
 
-        self.send().direct_egld(&commitment.recipient, &commitment.amount);
-        commitment.status = STATUS_CLAIMED;
-        self.commitments(id).set(commitment);
+        if commitment.state.status != CommitmentStatus::Failed {
+            self.mark_failed(&mut commitment, now);
+        }
+
+        let amount = self.remaining_amount(&commitment);
+        self.send().direct_egld(&commitment.terms.fallback, &amount);
+        commitment.state.status = CommitmentStatus::Swept;
+        self.open_ids_by_recipient(&commitment.terms.recipient).swap_remove(&id);
+        self.save_state(&commitment);
 
-        self.claimed_event(id);
+        self.swept_event(
+            id,
+            &commitment.terms.fallback,
+            &amount,
+            self.event_data(commitment.state.status),
+        );
     }
 
+    /// Lets the recipient of a failed commitment waive `amount` of the forfeited
+    /// stake (all of it by default), refunding it to the creator straight away.
+    #[endpoint(forgive)]
+    fn forgive(&self, id: u64, amount_opt: OptionalValue<BigUint>) {
+        let mut commitment = self.get_stored_or_fail(id);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == commitment.terms.recipient, "Only recipient can forgive");
+        require!(
+            commitment.state.status == CommitmentStatus::Failed,
+            "Commitment is not failed"
+        );
+
+        let remaining = self.remaining_amount(&commitment);
+        let amount = match amount_opt {
+            OptionalValue::Some(value) => value,
+            OptionalValue::None => remaining.clone(),
+        };
+        require!(amount > 0u64, "Amount must be > 0");
+        require!(amount <= remaining, "Amount exceeds remaining stake");
+
+        self.send().direct_egld(&commitment.terms.creator, &amount);
+        commitment.state.forgiven_amount += &amount;
+        if amount == remaining {
+            commitment.state.status = CommitmentStatus::Forgiven;
+            self.open_ids_by_recipient(&commitment.terms.recipient).swap_remove(&id);
+        }
+        self.save_state(&commitment);
+
+        self.forgiven_event(
+            id,
+            &commitment.terms.creator,
+            &amount,
+            self.event_data(commitment.state.status),
+        );
+    }
+
+    /// Lets the creator walk away before the deadline. Free within the grace
+    /// period after creation; afterwards `cancel_penalty_bps` of the stake goes
+    /// to the recipient and the rest is refunded.
     #[endpoint(cancel)]
     fn cancel(&self, id: u64) {
-        let mut commitment = self.get_commitment_or_fail(id);
+        let mut commitment = self.get_stored_or_fail(id);
 
         let caller = self.blockchain().get_caller();
-        require!(caller == commitment.creator, "Only creator can cancel");
-        require!(commitment.status == STATUS_ACTIVE, "Commitment is not active");
+        require!(caller == commitment.terms.creator, "Only creator can cancel");
+        require!(commitment.terms.cancellable, "Cancellation disabled");
+        require!(
+            commitment.state.status == CommitmentStatus::Active,
+            "Commitment is not active"
+        );
 
         let now = self.now();
-        require!(now < commitment.deadline, "Deadline already reached");
-        require!(commitment.proof_submitted_at == 0, "Proof already submitted");
+        require!(now < commitment.terms.deadline, "Deadline already reached");
+        require!(commitment.state.proof_submitted_at == 0, "Proof already submitted");
 
-        self.send().direct_egld(&commitment.creator, &commitment.amount);
-        commitment.status = STATUS_REFUNDED;
-        commitment.finalized_at = now;
-        self.commitments(id).set(commitment);
+        let penalty = self.cancel_penalty(&commitment, now);
+        let refund = &commitment.terms.amount - &penalty;
+        if penalty > 0u64 {
+            self.send().direct_egld(&commitment.terms.recipient, &penalty);
+        }
+        self.send().direct_egld(&commitment.terms.creator, &refund);
+        commitment.state.status = CommitmentStatus::Refunded;
+        commitment.state.finalized_at = now;
+        self.open_ids_by_recipient(&commitment.terms.recipient).swap_remove(&id);
+        self.save_state(&commitment);
 
-        self.cancelled_event(id);
+        self.cancelled_event(id, &refund, &penalty, self.event_data(commitment.state.status));
+    }
+
+    /// Lets the creator give up on an active or sealed commitment that can
+    /// still complete, without waiting for it to lapse: it fails now, and the
+    /// claim cooldown starts now.
+    #[endpoint(concede)]
+    fn concede(&self, id: u64) {
+        let mut commitment = self.get_stored_or_fail(id);
+
+        let caller = self.blockchain().get_caller();
+        require!(caller == commitment.terms.creator, "Only creator can concede");
+        require!(
+            matches!(
+                commitment.state.status,
+                CommitmentStatus::Active | CommitmentStatus::Sealed
+            ),
+            "Commitment is not active"
+        );
+        let now = self.now();
+        require!(!self.is_lapsed(&commitment, now), "Commitment already lapsed");
+
+        commitment.state.status = CommitmentStatus::Failed;
+        commitment.state.finalized_at = now;
+        self.save_state(&commitment);
+
+        self.conceded_event(id, self.event_data(commitment.state.status));
+    }
+
+    /// The compact record of a pruned commitment; empty for ids never pruned.
+    #[view(get_archived_commitment)]
+    fn get_archived_commitment(&self, id: u64) -> OptionalValue<ArchivedCommitment<Self::Api>> {
+        if self.archived_commitments(id).is_empty() {
+            return OptionalValue::None;
+        }
+        OptionalValue::Some(self.archived_commitments(id).get())
+    }
+
+    #[view(is_pruned)]
+    fn is_pruned(&self, id: u64) -> bool {
+        !self.archived_commitments(id).is_empty()
+    }
+
+    #[view(get_archive_retention_seconds)]
+    fn get_archive_retention_seconds(&self) -> u64 {
+        if self.archive_retention_seconds().is_empty() {
+            return DEFAULT_ARCHIVE_RETENTION_SECONDS;
+        }
+        self.archive_retention_seconds().get()
     }
 
     #[view(get_commitment)]
@@ -204,20 +1211,127 @@ pub trait PublicCommitmentFund {
         self.get_commitment_or_fail(id)
     }
 
+    /// Every proof revision of a commitment, oldest first; the last entry is the
+    /// one reflected in `get_commitment`.
+    #[view(get_proof_history)]
+    fn get_proof_history(&self, id: u64) -> MultiValueEncoded<ProofEntry<Self::Api>> {
+        let commitment = self.get_commitment_or_fail(id);
+        let mut result = MultiValueEncoded::new();
+
+        let history = self.proof_history(id);
+        if history.is_empty() {
+            // Proofs submitted before revisions were tracked only live on the commitment.
+            if commitment.proof_submitted_at > 0 && !commitment.proof_url.is_empty() {
+                result.push(ProofEntry {
+                    kind: commitment.proof_kind,
+                    reference: commitment.proof_url,
+                    proof_hash: commitment.proof_hash,
+                    submitted_at: commitment.proof_submitted_at,
+                });
+            }
+            return result;
+        }
+
+        for entry in history.iter() {
+            result.push(entry);
+        }
+        result
+    }
+
+    /// Bytes an attester signs to vouch for `proof_url` on commitment `id`.
+    #[view(get_attestation_message)]
+    fn get_attestation_message(&self, id: u64, proof_url: ManagedBuffer) -> ManagedBuffer {
+        let hash = self.crypto().sha256(&proof_url);
+        self.attestation_message(id, hash.as_managed_buffer())
+    }
+
+    #[view(get_release_endpoint)]
+    fn get_release_endpoint(&self, recipient: ManagedAddress) -> ManagedBuffer {
+        self.release_endpoint(&recipient).get()
+    }
+
+    /// Ids are assigned sequentially from 1, so every id below `next_id` exists,
+    /// pruned ones included.
     #[view(get_total_ids)]
     fn get_total_ids(&self) -> u64 {
-        self.all_ids().len() as u64
+        self.next_id().get() - 1
     }
 
     #[view(get_ids_page)]
     fn get_ids_page(&self, start: u64, limit: u64) -> MultiValueEncoded<u64> {
         let mut result = MultiValueEncoded::new();
 
+        let total = self.get_total_ids();
+        if start >= total {
+            return result;
+        }
+
+        let end_inclusive = core::cmp::min(total, start.saturating_add(limit));
+        for id in start + 1..=end_inclusive {
+            result.push(id);
+        }
+
+        result
+    }
+
+    /// Number of commitments paying out to `recipient` that are not settled
+    /// yet, the positions `claim_all` pages over.
+    #[view(get_total_open_ids)]
+    fn get_total_open_ids(&self, recipient: ManagedAddress) -> u64 {
+        self.open_ids_by_recipient(&recipient).len() as u64
+    }
+
+    /// Ids of stored commitments created with `tag`, paginated like `get_ids_page`.
+    /// Oldest first, except that pruning moves the newest id into the pruned one's place.
+    #[view(get_ids_by_tag)]
+    fn get_ids_by_tag(&self, tag: ManagedBuffer, start: u64, limit: u64) -> MultiValueEncoded<u64> {
+        self.ids_page(&self.ids_by_tag(&tag), start, limit)
+    }
+
+    #[view(get_total_ids_by_tag)]
+    fn get_total_ids_by_tag(&self, tag: ManagedBuffer) -> u64 {
+        self.ids_by_tag(&tag).len() as u64
+    }
+
+    #[view(is_curated_tags_only)]
+    fn is_curated_tags_only(&self) -> bool {
+        self.curated_tags_only().get()
+    }
+
+    #[view(get_allowed_tags)]
+    fn get_allowed_tags(&self) -> MultiValueEncoded<ManagedBuffer> {
+        self.allowed_tags().iter().collect()
+    }
+
+    /// The commitments of `ids` still stored, in the order asked. Unknown and
+    /// pruned ids are left out; `is_pruned` tells them apart.
+    #[view(get_commitments_batch)]
+    fn get_commitments_batch(&self, ids: MultiValueEncoded<u64>) -> MultiValueEncoded<Commitment<Self::Api>> {
+        let mut result = MultiValueEncoded::new();
+
+        for id in ids.into_iter() {
+            if !self.commitment_exists(id) {
+                continue;
+            }
+            result.push(self.load_commitment(id));
+        }
+
+        result
+    }
+
+    fn ids_page(
+        &self,
+        ids: &UnorderedSetMapper<u64>,
+        start: u64,
+        limit: u64,
+    ) -> MultiValueEncoded<u64> {
+        let mut result = MultiValueEncoded::new();
+
         if limit == 0 {
             return result;
         }
 
-        let total = self.all_ids().len();
+        let total = ids.len();
         let start_zero_based = start as usize;
         if start_zero_based >= total {
             return result;
@@ -226,67 +1340,402 @@ pub trait PublicCommitmentFund {
         let end_exclusive = core::cmp::min(total, start_zero_based.saturating_add(limit as usize));
         for zero_index in start_zero_based..end_exclusive {
             let mapper_index = zero_index + 1;
-            result.push(self.all_ids().get(mapper_index));
+            result.push(ids.get_by_index(mapper_index));
         }
 
         result
     }
 
-    #[view(get_commitments_batch)]
-    fn get_commitments_batch(&self, ids: MultiValueEncoded<u64>) -> MultiValueEncoded<Commitment<Self::Api>> {
-        let mut result = MultiValueEncoded::new();
+    fn require_valid_tag(&self, tag: &ManagedBuffer) {
+        require!(tag.len() <= tags::MAX_TAG_BYTES, "Invalid tag");
+        let mut tag_bytes = [0u8; tags::MAX_TAG_BYTES];
+        require!(
+            tags::is_valid_tag(tag.load_to_byte_array(&mut tag_bytes)),
+            "Invalid tag"
+        );
+    }
 
-        for id in ids.into_iter() {
-            if self.commitments(id).is_empty() {
-                continue;
+    fn require_valid_commitment_tags(&self, tags: &ManagedVec<ManagedBuffer>) {
+        require!(tags.len() <= tags::MAX_TAGS, "Too many tags");
+
+        let curated = self.curated_tags_only().get();
+        for (index, tag) in tags.iter().enumerate() {
+            self.require_valid_tag(&tag);
+            require!(
+                !curated || self.allowed_tags().contains(&tag),
+                "Tag not allowed"
+            );
+            for earlier in tags.iter().take(index) {
+                require!(*earlier != *tag, "Duplicate tag");
             }
-            result.push(self.commitments(id).get());
         }
+    }
 
-        result
+    fn require_can_submit_proof(&self, commitment: &StoredCommitment<Self::Api>) {
+        require!(
+            self.caller_acts_for(&commitment.terms.creator, DelegationKind::Proof),
+            "Only creator can submit proof"
+        );
+        // Creators may revise their own proofs, but an attester's or oracle's
+        // ruling stands.
+        if commitment.state.status == CommitmentStatus::Completed {
+            require!(
+                commitment.terms.attester.is_zero() && commitment.terms.oracle.is_zero(),
+                "Verified proof cannot be revised"
+            );
+        } else {
+            require!(
+                commitment.state.status == CommitmentStatus::Active,
+                "Commitment is not active"
+            );
+        }
+        self.require_proof_window_open(commitment);
     }
 
-    fn get_commitment_or_fail(&self, id: u64) -> Commitment<Self::Api> {
-        require!(!self.commitments(id).is_empty(), "Commitment not found");
-        self.commitments(id).get()
+    /// Proofs the creator vouches for alone are refused when an attester or
+    /// oracle has to confirm the goal.
+    fn require_self_reported(&self, commitment: &StoredCommitment<Self::Api>) {
+        require!(commitment.terms.attester.is_zero(), "Attested proof required");
+        require!(commitment.terms.oracle.is_zero(), "Oracle verification required");
     }
 
-    fn now(&self) -> u64 {
-        self.blockchain().get_block_timestamp_seconds().as_u64_seconds()
+    /// Proofs are only accepted between `start_at` and the deadline, both inclusive.
+    fn require_proof_window_open(&self, commitment: &StoredCommitment<Self::Api>) {
+        let now = self.now();
+        require!(now >= commitment.terms.start_at, "Proof window not open");
+        require!(now <= commitment.terms.deadline, "Deadline passed");
     }
 
-    #[event("CommitmentCreated")]
-    fn commitment_created_event(
+    fn record_proof(
         &self,
-        #[indexed] id: u64,
-        #[indexed] creator: ManagedAddress,
-        #[indexed] recipient: ManagedAddress,
-        #[indexed] amount: BigUint,
-        #[indexed] deadline: u64,
-        #[indexed] cooldown: u64,
-    );
+        commitment: &mut StoredCommitment<Self::Api>,
+        kind: ProofKind,
+        reference: ManagedBuffer,
+        proof_hash: ManagedBuffer,
+    ) {
+        let is_revision = commitment.state.status == CommitmentStatus::Completed;
 
-    #[event("ProofSubmitted")]
-    fn proof_submitted_event(&self, #[indexed] id: u64, proof_hash: ManagedBuffer);
+        commitment.state.proof_kind = kind;
+        commitment.state.proof_submitted_at = self.now();
+        commitment.state.status = CommitmentStatus::Completed;
+        let proof = CommitmentProof {
+            proof_url: reference,
+            proof_hash,
+        };
 
-    #[event("FailedFinalized")]
-    fn failed_finalized_event(&self, #[indexed] id: u64);
+        self.save_state(commitment);
+        self.commitment_proof(commitment.id).set(&proof);
+        let revision = self.push_proof_history(commitment, &proof);
+        let data = self.event_data(commitment.state.status);
+        if is_revision {
+            self.proof_revised_event(
+                commitment.id,
+                revision,
+                kind,
+                &proof.proof_url,
+                &proof.proof_hash,
+                data,
+            );
+        } else {
+            self.proof_submitted_event(
+                commitment.id,
+                kind,
+                &proof.proof_url,
+                &proof.proof_hash,
+                data,
+            );
+        }
+    }
 
-    #[event("Refunded")]
-    fn refunded_event(&self, #[indexed] id: u64);
+    /// Appends the commitment's current proof to its history and returns the
+    /// entry's 1-based revision number.
+    fn push_proof_history(
+        &self,
+        commitment: &StoredCommitment<Self::Api>,
+        proof: &CommitmentProof<Self::Api>,
+    ) -> u64 {
+        let entry = ProofEntry {
+            kind: commitment.state.proof_kind,
+            reference: proof.proof_url.clone(),
+            proof_hash: proof.proof_hash.clone(),
+            submitted_at: commitment.state.proof_submitted_at,
+        };
+        self.proof_history(commitment.id).push(&entry) as u64
+    }
 
-    #[event("Claimed")]
-    fn claimed_event(&self, #[indexed] id: u64);
+    /// `contract address ++ id (8 bytes, big-endian) ++ sha256(proof_url)`.
+    fn attestation_message(&self, id: u64, proof_hash: &ManagedBuffer) -> ManagedBuffer {
+        let mut message = self.blockchain().get_sc_address().as_managed_buffer().clone();
+        message.append_bytes(&id.to_be_bytes());
+        message.append(proof_hash);
+        message
+    }
+
+    fn commitment_exists(&self, id: u64) -> bool {
+        self.commitment_version(id).get() == STORAGE_VERSION || !self.commitments(id).is_empty()
+    }
 
-    #[event("Cancelled")]
-    fn cancelled_event(&self, #[indexed] id: u64);
+    fn get_commitment_or_fail(&self, id: u64) -> Commitment<Self::Api> {
+        self.require_commitment_exists(id);
+        self.load_commitment(id)
+    }
+
+    fn require_commitment_exists(&self, id: u64) {
+        if !self.commitment_exists(id) {
+            require!(self.archived_commitments(id).is_empty(), "Commitment pruned");
+            sc_panic!("Commitment not found");
+        }
+    }
+
+    /// Loads a commitment for an endpoint to act on. Records still in an older
+    /// layout are moved to the current one first.
+    fn get_stored_or_fail(&self, id: u64) -> StoredCommitment<Self::Api> {
+        if self.commitment_version(id).get() != STORAGE_VERSION {
+            self.require_commitment_exists(id);
+            self.migrate_commitment(id);
+        }
+        self.load_stored(id)
+    }
+
+    /// Rewrites an older record in the current layout, indexing it for
+    /// `claim_all` if it can still pay out; older layouts predate that index.
+    fn migrate_commitment(&self, id: u64) {
+        let commitment = self.load_commitment(id);
+        if matches!(
+            commitment.status,
+            CommitmentStatus::Active
+                | CommitmentStatus::Completed
+                | CommitmentStatus::Failed
+                | CommitmentStatus::Sealed
+                | CommitmentStatus::Verifying
+                | CommitmentStatus::Releasing
+        ) {
+            self.open_ids_by_recipient(&commitment.recipient).insert(id);
+        }
+        self.store_commitment(&commitment);
+        self.commitments(id).clear();
+    }
+
+    /// Terms and state of a commitment known to be on the current layout.
+    fn load_stored(&self, id: u64) -> StoredCommitment<Self::Api> {
+        StoredCommitment {
+            id,
+            terms: self.commitment_terms(id).get(),
+            state: self.commitment_state(id).get(),
+        }
+    }
+
+    /// Decodes a stored commitment whatever layout version it was written with.
+    fn load_commitment(&self, id: u64) -> Commitment<Self::Api> {
+        match self.commitment_version(id).get() {
+            STORAGE_VERSION => {
+                let proof = if self.commitment_proof(id).is_empty() {
+                    CommitmentProof::default()
+                } else {
+                    self.commitment_proof(id).get()
+                };
+                Commitment::from_parts(
+                    id,
+                    self.commitment_terms(id).get(),
+                    self.commitment_metadata(id).get(),
+                    self.commitment_state(id).get(),
+                    proof,
+                )
+            },
+            1 => self.commitments(id).get(),
+            _ => self.legacy_commitments(id).get().into_current(),
+        }
+    }
+
+    /// Writes every part of `commitment` in the current layout. The proof is
+    /// left unset until there is one.
+    fn store_commitment(&self, commitment: &Commitment<Self::Api>) {
+        let id = commitment.id;
+        self.commitment_terms(id).set(commitment.terms());
+        self.commitment_metadata(id).set(commitment.metadata());
+        self.commitment_state(id).set(commitment.state());
+        if !commitment.proof_url.is_empty() || !commitment.proof_hash.is_empty() {
+            self.commitment_proof(id).set(commitment.proof());
+        }
+        self.commitment_version(id).set(STORAGE_VERSION);
+    }
+
+    /// Writes back the only part of a commitment endpoints change after creation,
+    /// besides the proof.
+    fn save_state(&self, commitment: &StoredCommitment<Self::Api>) {
+        self.commitment_state(commitment.id).set(&commitment.state);
+        if self.is_settled(commitment.state.status) && self.settled_at(commitment.id).is_empty() {
+            self.settled_at(commitment.id).set(self.now());
+        }
+    }
+
+    /// Refunded, claimed, swept or fully forgiven: nothing left to pay out.
+    fn is_settled(&self, status: CommitmentStatus) -> bool {
+        matches!(
+            status,
+            CommitmentStatus::Refunded
+                | CommitmentStatus::Claimed
+                | CommitmentStatus::Swept
+                | CommitmentStatus::Forgiven
+        )
+    }
+
+    /// When a settled commitment reached its final status. Records settled
+    /// before this was tracked fall back to `finalized_at`.
+    fn settled_time(&self, id: u64, finalized_at: u64) -> u64 {
+        if self.settled_at(id).is_empty() {
+            return finalized_at;
+        }
+        self.settled_at(id).get()
+    }
+
+    /// Moment after which an unsettled commitment can no longer complete: the
+    /// deadline, or the end of the reveal window once a proof has been sealed.
+    fn settle_after(&self, commitment: &StoredCommitment<Self::Api>) -> u64 {
+        if commitment.state.status == CommitmentStatus::Sealed {
+            commitment.terms.deadline + REVEAL_WINDOW_SECONDS
+        } else {
+            commitment.terms.deadline
+        }
+    }
+
+    /// Failed commitments are claimable a cooldown after `finalize`; lapsed ones
+    /// nobody finalized count their cooldown from `settle_after` instead.
+    fn claim_time(&self, commitment: &StoredCommitment<Self::Api>) -> u64 {
+        let cooldown_start = if commitment.state.finalized_at > 0 {
+            commitment.state.finalized_at
+        } else {
+            self.settle_after(commitment)
+        };
+        cooldown_start.saturating_add(commitment.terms.cooldown_seconds)
+    }
+
+    fn claim_expiry(&self, commitment: &StoredCommitment<Self::Api>) -> u64 {
+        self.claim_time(commitment)
+            .saturating_add(commitment.terms.claim_window_seconds)
+    }
+
+    /// Active or sealed commitment that can no longer complete but was never finalized.
+    fn is_lapsed(&self, commitment: &StoredCommitment<Self::Api>, now: u64) -> bool {
+        matches!(
+            commitment.state.status,
+            CommitmentStatus::Active | CommitmentStatus::Sealed
+        ) && now > self.settle_after(commitment)
+    }
+
+    fn require_claimable(&self, commitment: &StoredCommitment<Self::Api>, now: u64) {
+        require!(
+            commitment.state.status == CommitmentStatus::Failed
+                || self.is_lapsed(commitment, now),
+            "Commitment is not failed"
+        );
+        require!(now >= self.claim_time(commitment), "Cooldown not reached");
+        require!(now < self.claim_expiry(commitment), "Claim window expired");
+    }
+
+    fn is_claimable(&self, commitment: &StoredCommitment<Self::Api>, now: u64) -> bool {
+        (commitment.state.status == CommitmentStatus::Failed
+            || self.is_lapsed(commitment, now))
+            && now >= self.claim_time(commitment)
+            && now < self.claim_expiry(commitment)
+    }
+
+    /// Share of the stake a cancellation at `now` forfeits to the recipient.
+    fn cancel_penalty(&self, commitment: &StoredCommitment<Self::Api>, now: u64) -> BigUint {
+        let terms = &commitment.terms;
+        if now <= terms.created_at.saturating_add(terms.cancel_grace_seconds) {
+            return BigUint::zero();
+        }
+        &terms.amount * terms.cancel_penalty_bps / MAX_BPS
+    }
+
+    fn remaining_amount(&self, commitment: &StoredCommitment<Self::Api>) -> BigUint {
+        &commitment.terms.amount - &commitment.state.forgiven_amount
+    }
+
+    fn mark_failed(&self, commitment: &mut StoredCommitment<Self::Api>, finalized_at: u64) {
+        commitment.state.status = CommitmentStatus::Failed;
+        commitment.state.finalized_at = finalized_at;
+        self.failed_finalized_event(
+            commitment.id,
+            finalized_at,
+            self.event_data(commitment.state.status),
+        );
+    }
+
+    fn mark_claimed(&self, commitment: &mut StoredCommitment<Self::Api>) {
+        commitment.state.status = CommitmentStatus::Claimed;
+        self.open_ids_by_recipient(&commitment.terms.recipient)
+            .swap_remove(&commitment.id);
+        self.save_state(commitment);
+        self.claimed_event(
+            commitment.id,
+            &commitment.terms.recipient,
+            &self.remaining_amount(commitment),
+            self.event_data(commitment.state.status),
+        );
+    }
+
+    fn now(&self) -> u64 {
+        self.blockchain().get_block_timestamp_seconds().as_u64_seconds()
+    }
 
     #[storage_mapper("next_id")]
     fn next_id(&self) -> SingleValueMapper<u64>;
 
+    #[storage_mapper("commitment_terms")]
+    fn commitment_terms(&self, id: u64) -> SingleValueMapper<CommitmentTerms<Self::Api>>;
+
+    #[storage_mapper("commitment_metadata")]
+    fn commitment_metadata(&self, id: u64) -> SingleValueMapper<CommitmentMetadata<Self::Api>>;
+
+    #[storage_mapper("commitment_state")]
+    fn commitment_state(&self, id: u64) -> SingleValueMapper<CommitmentState<Self::Api>>;
+
+    /// Empty until a proof is submitted, sealed or verified.
+    #[storage_mapper("commitment_proof")]
+    fn commitment_proof(&self, id: u64) -> SingleValueMapper<CommitmentProof<Self::Api>>;
+
+    #[storage_mapper("proof_history")]
+    fn proof_history(&self, id: u64) -> VecMapper<ProofEntry<Self::Api>>;
+
+    /// Whole records written by layout 1; cleared once moved to the current layout.
     #[storage_mapper("commitments")]
     fn commitments(&self, id: u64) -> SingleValueMapper<Commitment<Self::Api>>;
 
-    #[storage_mapper("all_ids")]
-    fn all_ids(&self) -> VecMapper<u64>;
+    /// Same key as `commitments`, read for records without a layout version.
+    #[storage_mapper("commitments")]
+    fn legacy_commitments(&self, id: u64) -> SingleValueMapper<CommitmentV0<Self::Api>>;
+
+    /// Layout a commitment was written with; 0 for records from the first deployment.
+    #[storage_mapper("commitment_version")]
+    fn commitment_version(&self, id: u64) -> SingleValueMapper<u8>;
+
+    /// Ids of unpruned commitments created with `tag`.
+    #[storage_mapper("ids_by_tag")]
+    fn ids_by_tag(&self, tag: &ManagedBuffer) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("curated_tags_only")]
+    fn curated_tags_only(&self) -> SingleValueMapper<bool>;
+
+    #[storage_mapper("allowed_tags")]
+    fn allowed_tags(&self) -> UnorderedSetMapper<ManagedBuffer>;
+
+    /// Ids of commitments that may still pay out to `recipient`; settled ones are removed.
+    #[storage_mapper("open_ids_by_recipient")]
+    fn open_ids_by_recipient(&self, recipient: &ManagedAddress) -> UnorderedSetMapper<u64>;
+
+    #[storage_mapper("settled_at")]
+    fn settled_at(&self, id: u64) -> SingleValueMapper<u64>;
+
+    #[storage_mapper("archived_commitments")]
+    fn archived_commitments(&self, id: u64) -> SingleValueMapper<ArchivedCommitment<Self::Api>>;
+
+    /// Unset until the owner changes it; see `get_archive_retention_seconds`.
+    #[storage_mapper("archive_retention_seconds")]
+    fn archive_retention_seconds(&self) -> SingleValueMapper<u64>;
+
+    /// Endpoint a contract recipient wants its released stakes delivered to.
+    #[storage_mapper("release_endpoint")]
+    fn release_endpoint(&self, recipient: &ManagedAddress) -> SingleValueMapper<ManagedBuffer>;
 }
@@ -0,0 +1,123 @@
+multiversx_sc::imports!();
+multiversx_sc::derive_imports!();
+
+pub const MAX_MEDIA_TYPE_BYTES: usize = 127;
+
+const CID_V0_LEN: usize = 46;
+const CID_V1_MIN_LEN: usize = 50;
+const CID_V1_MAX_LEN: usize = 100;
+const TX_HASH_HEX_LEN: usize = 64;
+
+/// What a proof reference points at. The reference itself is kept in
+/// `Commitment::proof_url`, whatever its kind.
+#[type_abi]
+#[derive(
+    TopEncode,
+    TopDecode,
+    NestedEncode,
+    NestedDecode,
+    ManagedVecItem,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Debug,
+)]
+pub enum ProofKind {
+    /// `http://` or `https://` URL.
+    HttpUrl,
+    /// IPFS CID, either v0 (`Qm...`, base58btc) or v1 (`b...`, base32).
+    IpfsCid,
+    /// Media type (`type/subtype`) of evidence identified only by its hash.
+    ContentHash,
+    /// Hex-encoded hash of an on-chain transaction.
+    TxHash,
+    /// Recorded by the contract when the commitment's oracle confirms the goal;
+    /// the reference is the oracle address. Never accepted from callers.
+    OracleAttestation,
+    /// `http://` or `https://` URL whose hash the commitment's attester signed.
+    /// Only recorded through `submit_attested_proof`.
+    AttestedUrl,
+}
+
+impl ProofKind {
+    pub fn is_valid_reference(&self, reference: &[u8]) -> bool {
+        match self {
+            ProofKind::HttpUrl => is_http_url(reference),
+            ProofKind::IpfsCid => is_ipfs_cid(reference),
+            ProofKind::ContentHash => is_media_type(reference),
+            ProofKind::TxHash => is_tx_hash(reference),
+            ProofKind::OracleAttestation | ProofKind::AttestedUrl => false,
+        }
+    }
+}
+
+/// Location of a commitment's off-chain metadata document: an `http(s)://`
+/// URL or `ipfs://` followed by a CID.
+pub fn is_metadata_uri(uri: &[u8]) -> bool {
+    match uri.strip_prefix(b"ipfs://") {
+        Some(cid) => is_ipfs_cid(cid),
+        None => is_http_url(uri),
+    }
+}
+
+fn is_http_url(reference: &[u8]) -> bool {
+    let rest = if let Some(rest) = reference.strip_prefix(b"https://") {
+        rest
+    } else if let Some(rest) = reference.strip_prefix(b"http://") {
+        rest
+    } else {
+        return false;
+    };
+    !rest.is_empty() && rest.iter().all(|byte| byte.is_ascii_graphic())
+}
+
+fn is_ipfs_cid(reference: &[u8]) -> bool {
+    if reference.len() == CID_V0_LEN && reference.starts_with(b"Qm") {
+        return reference.iter().all(|byte| is_base58_char(*byte));
+    }
+
+    match reference.split_first() {
+        Some((b'b', rest)) => {
+            (CID_V1_MIN_LEN..=CID_V1_MAX_LEN).contains(&reference.len())
+                && rest.iter().all(|byte| is_base32_char(*byte))
+        },
+        _ => false,
+    }
+}
+
+fn is_media_type(reference: &[u8]) -> bool {
+    if reference.len() > MAX_MEDIA_TYPE_BYTES {
+        return false;
+    }
+
+    let mut parts = reference.split(|byte| *byte == b'/');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(main), Some(sub), None) => is_media_type_token(main) && is_media_type_token(sub),
+        _ => false,
+    }
+}
+
+fn is_tx_hash(reference: &[u8]) -> bool {
+    reference.len() == TX_HASH_HEX_LEN
+        && reference
+            .iter()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(byte))
+}
+
+fn is_base58_char(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() && !matches!(byte, b'0' | b'O' | b'I' | b'l')
+}
+
+fn is_base32_char(byte: u8) -> bool {
+    byte.is_ascii_lowercase() || (b'2'..=b'7').contains(&byte)
+}
+
+fn is_media_type_token(token: &[u8]) -> bool {
+    !token.is_empty()
+        && token.iter().all(|byte| {
+            byte.is_ascii_lowercase()
+                || byte.is_ascii_digit()
+                || matches!(byte, b'!' | b'#' | b'$' | b'&' | b'^' | b'_' | b'.' | b'+' | b'-')
+        })
+}
@@ -0,0 +1,14 @@
+pub const MAX_TAGS: usize = 5;
+pub const MAX_TAG_BYTES: usize = 24;
+
+/// Tags are short lowercase slugs: ASCII letters, digits and inner hyphens,
+/// e.g. `fitness` or `open-source`.
+pub fn is_valid_tag(tag: &[u8]) -> bool {
+    !tag.is_empty()
+        && tag.len() <= MAX_TAG_BYTES
+        && !tag.starts_with(b"-")
+        && !tag.ends_with(b"-")
+        && tag
+            .iter()
+            .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || *byte == b'-')
+}
@@ -1,8 +1,8 @@
 use multiversx_sc::types::{ManagedAddress, ManagedBuffer, TimestampSeconds};
 use multiversx_sc_scenario::imports::*;
 use public_commitment_fund::{
-    PublicCommitmentFund, STATUS_ACTIVE, STATUS_CLAIMED, STATUS_COMPLETED, STATUS_FAILED,
-    STATUS_REFUNDED,
+    delegation::DelegationModule, CommitmentStatus, CommitmentV0, CreateOptions, CreatedEventData,
+    DelegationKind, EventData, ProofKind, PublicCommitmentFund, EVENT_VERSION, STORAGE_VERSION,
 };
 
 const INIT_TS: u64 = 1_000;
@@ -85,10 +85,9 @@ fn create_commitment_stores_data_and_id() {
     b_wrapper
         .execute_query(&sc_wrapper, |sc| {
             assert_eq!(sc.next_id().get(), 2u64);
-            assert_eq!(sc.all_ids().len(), 1usize);
-            assert_eq!(sc.all_ids().get(1), 1u64);
+            assert_eq!(sc.get_total_ids(), 1u64);
 
-            let c = sc.commitments(1).get();
+            let c = sc.load_commitment(1);
             assert_eq!(c.id, 1u64);
             assert_eq!(
                 c.creator,
@@ -101,9 +100,15 @@ fn create_commitment_stores_data_and_id() {
             assert_eq!(c.amount, BigUint::from(ONE_EGLD));
             assert_eq!(c.deadline, deadline);
             assert_eq!(c.cooldown_seconds, 86_400u64);
-            assert_eq!(c.status, STATUS_ACTIVE);
+            assert_eq!(c.status, CommitmentStatus::Active);
             assert_eq!(c.proof_submitted_at, 0u64);
             assert_eq!(c.finalized_at, 0u64);
+            assert_eq!(c.fallback, ManagedAddress::<DebugApi>::from_address(&creator));
+            assert_eq!(c.claim_window_seconds, 365 * 86_400u64);
+            assert_eq!(c.start_at, INIT_TS);
+            assert_eq!(c.cancel_grace_seconds, 86_400u64);
+            assert_eq!(c.cancel_penalty_bps, 1_000u64);
+            assert!(c.cancellable);
         })
         .assert_ok();
 }
@@ -163,7 +168,7 @@ fn create_validations_enforced() {
 }
 
 #[test]
-fn submit_proof_allows_deadline_boundary_and_blocks_overwrite() {
+fn submit_proof_allows_deadline_boundary() {
     let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
     let deadline = INIT_TS + 700;
     create_default(
@@ -185,19 +190,71 @@ fn submit_proof_allows_deadline_boundary_and_blocks_overwrite() {
 
     b_wrapper
         .execute_query(&sc_wrapper, |sc| {
-            let c = sc.commitments(1).get();
-            assert_eq!(c.status, STATUS_COMPLETED);
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Completed);
             assert_eq!(c.proof_submitted_at, deadline);
             assert_eq!(c.proof_hash.len(), 32usize);
         })
         .assert_ok();
 
-    // Overwrite must fail.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1));
     b_wrapper
         .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
             sc.submit_proof(1u64, mb(b"https://example.com/proof/overwrite"));
         })
-        .assert_user_error("Commitment is not active");
+        .assert_user_error("Deadline passed");
+}
+
+#[test]
+fn proof_revisions_keep_history_until_deadline() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    create_default(
+        &mut b_wrapper,
+        &creator,
+        &recipient,
+        &sc_wrapper,
+        deadline,
+        ONE_EGLD,
+    );
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(1u64, mb(b"https://exmaple.com/typo"));
+        })
+        .assert_ok();
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(INIT_TS + 100));
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_typed_proof(
+                1u64,
+                ProofKind::ContentHash,
+                mb(b"application/pdf"),
+                ManagedByteArray::new_from_bytes(&[9u8; 32]),
+            );
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let history: Vec<_> = sc.get_proof_history(1u64).into_iter().collect();
+            assert_eq!(history.len(), 2usize);
+            assert_eq!(history[0].kind, ProofKind::HttpUrl);
+            assert_eq!(history[0].reference, mb(b"https://exmaple.com/typo"));
+            assert_eq!(history[0].submitted_at, INIT_TS);
+            assert_eq!(history[1].kind, ProofKind::ContentHash);
+            assert_eq!(history[1].proof_hash, mb(&[9u8; 32]));
+            assert_eq!(history[1].submitted_at, INIT_TS + 100);
+
+            // The commitment reflects the latest revision.
+            let c = sc.get_commitment(1u64);
+            assert_eq!(c.status, CommitmentStatus::Completed);
+            assert_eq!(c.proof_kind, ProofKind::ContentHash);
+            assert_eq!(c.proof_url, mb(b"application/pdf"));
+            assert_eq!(c.proof_submitted_at, INIT_TS + 100);
+        })
+        .assert_ok();
 }
 
 #[test]
@@ -229,6 +286,263 @@ fn submit_proof_rejects_after_deadline_and_non_creator() {
         .assert_user_error("Deadline passed");
 }
 
+#[test]
+fn proofs_only_accepted_from_start_time() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let start_at = INIT_TS + 500;
+    let deadline = INIT_TS + 1_000;
+
+    let create = |b_wrapper: &mut BlockchainStateWrapper, start_at: u64| {
+        b_wrapper.execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+            sc.create_commitment(
+                mb(b"Run a marathon in May"),
+                recipient.clone().into(),
+                deadline,
+                OptionalValue::Some(CreateOptions {
+                    start_at: Some(start_at),
+                    ..CreateOptions::default()
+                }),
+            );
+        })
+    };
+
+    create(&mut b_wrapper, INIT_TS - 1).assert_user_error("Start time in the past");
+    create(&mut b_wrapper, deadline - 300)
+        .assert_user_error("Start time too close to deadline");
+    create(&mut b_wrapper, u64::MAX - 100)
+        .assert_user_error("Start time too close to deadline");
+    create(&mut b_wrapper, start_at).assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert_eq!(sc.get_commitment(1u64).start_at, start_at);
+        })
+        .assert_ok();
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(start_at - 1));
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(1u64, mb(b"https://example.com/proof"));
+        })
+        .assert_user_error("Proof window not open");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_sealed_proof(1u64, ManagedByteArray::new_from_bytes(&[7u8; 32]));
+        })
+        .assert_user_error("Proof window not open");
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(start_at));
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(1u64, mb(b"https://example.com/proof"));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn typed_proof_validates_reference_per_kind() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    for _ in 0..4u64 {
+        create_default(
+            &mut b_wrapper,
+            &creator,
+            &recipient,
+            &sc_wrapper,
+            deadline,
+            ONE_EGLD,
+        );
+    }
+
+    let evidence_hash = [7u8; 32];
+    let rejected: [(ProofKind, &[u8]); 6] = [
+        (ProofKind::HttpUrl, b"ftp://example.com/proof"),
+        (ProofKind::HttpUrl, b"https://"),
+        (ProofKind::IpfsCid, b"Qm0000000000000000000000000000000000000000000O"),
+        (ProofKind::ContentHash, b"application"),
+        (ProofKind::ContentHash, b"Image/PNG"),
+        (ProofKind::TxHash, b"0xabcdef"),
+    ];
+    for (kind, reference) in rejected {
+        b_wrapper
+            .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+                sc.submit_typed_proof(
+                    1u64,
+                    kind,
+                    mb(reference),
+                    ManagedByteArray::new_from_bytes(&evidence_hash),
+                );
+            })
+            .assert_user_error("Invalid proof reference");
+    }
+
+    let accepted: [(u64, ProofKind, &[u8]); 4] = [
+        (1, ProofKind::HttpUrl, b"https://example.com/proof"),
+        (
+            2,
+            ProofKind::IpfsCid,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+        ),
+        (3, ProofKind::ContentHash, b"application/pdf"),
+        (
+            4,
+            ProofKind::TxHash,
+            b"178acf0af558fb1e0da48a1064c7b43ac4cb79df9e9981e2e5b6779437a9ba7f",
+        ),
+    ];
+    for (id, kind, reference) in accepted {
+        b_wrapper
+            .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+                sc.submit_typed_proof(
+                    id,
+                    kind,
+                    mb(reference),
+                    ManagedByteArray::new_from_bytes(&evidence_hash),
+                );
+            })
+            .assert_ok();
+    }
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            for (id, kind, reference) in accepted {
+                let c = sc.load_commitment(id);
+                assert_eq!(c.status, CommitmentStatus::Completed);
+                assert_eq!(c.proof_kind, kind);
+                assert_eq!(c.proof_url, mb(reference));
+                assert_eq!(c.proof_hash, mb(&evidence_hash));
+            }
+        })
+        .assert_ok();
+}
+
+#[test]
+fn sealed_proof_reveals_after_deadline() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    create_default(
+        &mut b_wrapper,
+        &creator,
+        &recipient,
+        &sc_wrapper,
+        deadline,
+        ONE_EGLD,
+    );
+
+    let proof_url: &[u8] = b"https://example.com/exam-results";
+    let salt: &[u8] = b"0123456789abcdef";
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            let mut preimage = mb(proof_url);
+            preimage.append(&mb(salt));
+            let proof_hash = sc.crypto().sha256(&preimage);
+            sc.submit_sealed_proof(1u64, proof_hash);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Sealed);
+            assert!(c.proof_url.is_empty());
+            assert_eq!(c.proof_hash.len(), 32usize);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.cancel(1u64);
+        })
+        .assert_user_error("Commitment is not active");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.reveal_proof(1u64, mb(proof_url), mb(salt));
+        })
+        .assert_user_error("Reveal window not open");
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.finalize(1u64);
+        })
+        .assert_user_error("Reveal window open");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.reveal_proof(1u64, mb(proof_url), mb(b"fedcba9876543210"));
+        })
+        .assert_user_error("Proof does not match sealed hash");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.reveal_proof(1u64, mb(proof_url), mb(salt));
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.finalize(1u64);
+        })
+        .assert_ok();
+
+    b_wrapper.check_egld_balance(&creator, &rust_biguint!(10 * ONE_EGLD));
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Refunded);
+            assert_eq!(c.proof_url, mb(proof_url));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn unrevealed_sealed_proof_fails_after_reveal_window() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    let reveal_end = deadline + 86_400;
+    create_default(
+        &mut b_wrapper,
+        &creator,
+        &recipient,
+        &sc_wrapper,
+        deadline,
+        ONE_EGLD,
+    );
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            let proof_hash = sc.crypto().sha256(mb(b"never revealed"));
+            sc.submit_sealed_proof(1u64, proof_hash);
+        })
+        .assert_ok();
+
+    // The cooldown of an unrevealed proof only starts once the reveal window closes.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 86_400));
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(1u64);
+        })
+        .assert_user_error("Commitment is not failed");
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(reveal_end + 1));
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.reveal_proof(1u64, mb(b"never revealed"), mb(b"0123456789abcdef"));
+        })
+        .assert_user_error("Reveal window closed");
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.finalize(1u64);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Failed);
+            assert_eq!(c.finalized_at, reveal_end + 1);
+        })
+        .assert_ok();
+}
+
 #[test]
 fn finalize_active_marks_failed_and_double_finalize_reverts() {
     let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
@@ -257,8 +571,8 @@ fn finalize_active_marks_failed_and_double_finalize_reverts() {
 
     b_wrapper
         .execute_query(&sc_wrapper, |sc| {
-            let c = sc.commitments(1).get();
-            assert_eq!(c.status, STATUS_FAILED);
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Failed);
             assert_eq!(c.finalized_at, deadline + 1);
         })
         .assert_ok();
@@ -299,8 +613,8 @@ fn finalize_completed_refunds_creator() {
 
     b_wrapper
         .execute_query(&sc_wrapper, |sc| {
-            let c = sc.commitments(1).get();
-            assert_eq!(c.status, STATUS_REFUNDED);
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Refunded);
             assert_eq!(c.finalized_at, deadline + 1);
         })
         .assert_ok();
@@ -347,8 +661,8 @@ fn claim_requires_failed_and_cooldown() {
 
     b_wrapper
         .execute_query(&sc_wrapper, |sc| {
-            let c = sc.commitments(1).get();
-            assert_eq!(c.status, STATUS_CLAIMED);
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Claimed);
         })
         .assert_ok();
 
@@ -360,7 +674,7 @@ fn claim_requires_failed_and_cooldown() {
 }
 
 #[test]
-fn cancel_only_creator_before_deadline() {
+fn claim_settles_expired_commitment_without_finalize() {
     let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
     let deadline = INIT_TS + 700;
     create_default(
@@ -372,54 +686,518 @@ fn cancel_only_creator_before_deadline() {
         ONE_EGLD,
     );
 
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline));
     b_wrapper
-        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
-            sc.cancel(1u64);
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(1u64);
         })
-        .assert_user_error("Only creator can cancel");
+        .assert_user_error("Commitment is not failed");
 
+    // Cooldown runs from the deadline when nobody called finalize.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 86_399));
     b_wrapper
-        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
-            sc.cancel(1u64);
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(1u64);
+        })
+        .assert_user_error("Cooldown not reached");
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 86_400));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(1u64);
+        })
+        .assert_user_error("Only recipient can claim");
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(1u64);
         })
         .assert_ok();
 
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(11 * ONE_EGLD));
     b_wrapper
         .execute_query(&sc_wrapper, |sc| {
-            let c = sc.commitments(1).get();
-            assert_eq!(c.status, STATUS_REFUNDED);
-            assert_eq!(c.finalized_at, INIT_TS);
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Claimed);
+            assert_eq!(c.finalized_at, deadline + 86_400);
         })
         .assert_ok();
 }
 
 #[test]
-fn cancel_after_deadline_reverts() {
-    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+fn sweep_sends_unclaimed_stake_to_fallback_after_claim_window() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
     let deadline = INIT_TS + 700;
-    create_default(
-        &mut b_wrapper,
-        &creator,
-        &recipient,
-        &sc_wrapper,
-        deadline,
-        ONE_EGLD,
-    );
+    let fallback = b_wrapper.create_user_account(&rust_biguint!(0));
 
-    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline));
     b_wrapper
         .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
-            sc.cancel(1u64);
+            sc.create_commitment(
+                mb(b"Title"),
+                recipient.clone().into(),
+                deadline,
+                OptionalValue::Some(CreateOptions {
+                    claim_window_seconds: Some(0u64),
+                    ..CreateOptions::default()
+                }),
+            );
         })
-        .assert_user_error("Deadline already reached");
-}
+        .assert_user_error("Amount must be > 0");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+            sc.create_commitment(
+                mb(b"Title"),
+                recipient.clone().into(),
+                deadline,
+                OptionalValue::Some(CreateOptions {
+                    claim_window_seconds: Some(0u64),
+                    ..CreateOptions::default()
+                }),
+            );
+        })
+        .assert_user_error("Claim window must be > 0");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+            sc.create_commitment(
+                mb(b"Title"),
+                recipient.clone().into(),
+                deadline,
+                OptionalValue::Some(CreateOptions {
+                    claim_window_seconds: Some(1_000u64),
+                    fallback: Some(ManagedAddress::zero()),
+                    ..CreateOptions::default()
+                }),
+            );
+        })
+        .assert_user_error("Fallback cannot be zero address");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+            sc.create_commitment(
+                mb(b"Title"),
+                recipient.clone().into(),
+                deadline,
+                OptionalValue::Some(CreateOptions {
+                    claim_window_seconds: Some(1_000u64),
+                    fallback: Some(fallback.clone().into()),
+                    ..CreateOptions::default()
+                }),
+            );
+        })
+        .assert_ok();
 
-#[test]
-fn view_pagination_and_batch() {
-    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let expiry = deadline + 86_400 + 1_000;
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(expiry - 1));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.sweep(1u64);
+        })
+        .assert_user_error("Claim window not expired");
 
-    for i in 0..3u64 {
-        create_default(
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(expiry));
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(1u64);
+        })
+        .assert_user_error("Claim window expired");
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.sweep(1u64);
+        })
+        .assert_ok();
+
+    b_wrapper.check_egld_balance(&fallback, &rust_biguint!(ONE_EGLD));
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Swept);
+            assert_eq!(c.finalized_at, expiry);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.sweep(1u64);
+        })
+        .assert_user_error("Commitment is not failed");
+}
+
+#[test]
+fn forgive_refunds_waived_part_to_creator() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    for _ in 0..2u64 {
+        create_default(
+            &mut b_wrapper,
+            &creator,
+            &recipient,
+            &sc_wrapper,
+            deadline,
+            ONE_EGLD,
+        );
+    }
+
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.forgive(1u64, OptionalValue::None);
+        })
+        .assert_user_error("Commitment is not failed");
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1));
+    for id in 1..=2u64 {
+        b_wrapper
+            .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+                sc.finalize(id);
+            })
+            .assert_ok();
+    }
+
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.forgive(1u64, OptionalValue::None);
+        })
+        .assert_user_error("Only recipient can forgive");
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.forgive(1u64, OptionalValue::Some(BigUint::from(ONE_EGLD + 1)));
+        })
+        .assert_user_error("Amount exceeds remaining stake");
+
+    // Partial waiver on #1, full waiver on #2.
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.forgive(1u64, OptionalValue::Some(BigUint::from(ONE_EGLD / 4)));
+        })
+        .assert_ok();
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.forgive(2u64, OptionalValue::None);
+        })
+        .assert_ok();
+    b_wrapper.check_egld_balance(&creator, &rust_biguint!(8 * ONE_EGLD + ONE_EGLD / 4 + ONE_EGLD));
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1 + 86_400));
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_all(10u64, OptionalValue::None);
+        })
+        .assert_ok();
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(10 * ONE_EGLD + ONE_EGLD * 3 / 4));
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let first = sc.load_commitment(1);
+            assert_eq!(first.status, CommitmentStatus::Claimed);
+            assert_eq!(first.forgiven_amount, BigUint::from(ONE_EGLD / 4));
+
+            let second = sc.load_commitment(2);
+            assert_eq!(second.status, CommitmentStatus::Forgiven);
+            assert_eq!(second.forgiven_amount, BigUint::from(ONE_EGLD));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn concede_fails_commitment_and_starts_cooldown() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    for _ in 0..2 {
+        create_default(
+            &mut b_wrapper,
+            &creator,
+            &recipient,
+            &sc_wrapper,
+            deadline,
+            ONE_EGLD,
+        );
+    }
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(2u64, mb(b"https://example.com/proof"));
+        })
+        .assert_ok();
+
+    let conceded_at = INIT_TS + 100;
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(conceded_at));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.concede(1u64);
+        })
+        .assert_user_error("Only creator can concede");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.concede(2u64);
+        })
+        .assert_user_error("Commitment is not active");
+
+    let conceded = b_wrapper.execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+        sc.concede(1u64);
+    });
+    conceded.assert_ok();
+    let log = conceded.result_logs.last().unwrap();
+    assert_eq!(log.topics[0], b"Conceded".to_vec());
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Failed);
+            assert_eq!(c.finalized_at, conceded_at);
+        })
+        .assert_ok();
+
+    // The cooldown runs from the concession, well before the deadline.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(conceded_at + 86_399));
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(1u64);
+        })
+        .assert_user_error("Cooldown not reached");
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(conceded_at + 86_400));
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(1u64);
+        })
+        .assert_ok();
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(11 * ONE_EGLD));
+}
+
+#[test]
+fn concede_accepts_sealed_commitments_until_they_lapse() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    let reveal_end = deadline + 86_400;
+    for _ in 0..3 {
+        create_default(
+            &mut b_wrapper,
+            &creator,
+            &recipient,
+            &sc_wrapper,
+            deadline,
+            ONE_EGLD,
+        );
+    }
+    for id in 2..=3u64 {
+        b_wrapper
+            .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+                let proof_hash = sc.crypto().sha256(mb(b"sealed"));
+                sc.submit_sealed_proof(id, proof_hash);
+            })
+            .assert_ok();
+    }
+
+    // Past the deadline an active commitment can only fail through `finalize`,
+    // which dates the failure; a sealed one can still be revealed.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1));
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.concede(1u64);
+        })
+        .assert_user_error("Commitment already lapsed");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.concede(2u64);
+        })
+        .assert_ok();
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(reveal_end + 1));
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.concede(3u64);
+        })
+        .assert_user_error("Commitment already lapsed");
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let conceded = sc.load_commitment(2);
+            assert_eq!(conceded.status, CommitmentStatus::Failed);
+            assert_eq!(conceded.finalized_at, deadline + 1);
+            assert_eq!(sc.load_commitment(1).status, CommitmentStatus::Active);
+            assert_eq!(sc.load_commitment(3).status, CommitmentStatus::Sealed);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn cancel_only_creator_before_deadline() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    create_default(
+        &mut b_wrapper,
+        &creator,
+        &recipient,
+        &sc_wrapper,
+        deadline,
+        ONE_EGLD,
+    );
+
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.cancel(1u64);
+        })
+        .assert_user_error("Only creator can cancel");
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.cancel(1u64);
+        })
+        .assert_ok();
+    b_wrapper.check_egld_balance(&creator, &rust_biguint!(10 * ONE_EGLD));
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Refunded);
+            assert_eq!(c.finalized_at, INIT_TS);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn cancel_after_deadline_reverts() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    create_default(
+        &mut b_wrapper,
+        &creator,
+        &recipient,
+        &sc_wrapper,
+        deadline,
+        ONE_EGLD,
+    );
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline));
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.cancel(1u64);
+        })
+        .assert_user_error("Deadline already reached");
+}
+
+#[test]
+fn cancel_after_grace_period_pays_penalty_to_recipient() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 10_000;
+
+    for cancellable in [true, false] {
+        b_wrapper
+            .execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+                sc.create_commitment(
+                    mb(b"Ship weekly report"),
+                    recipient.clone().into(),
+                    deadline,
+                    OptionalValue::Some(CreateOptions {
+                        cancel_grace_seconds: Some(1_000u64),
+                        cancel_penalty_bps: Some(2_500u64),
+                        cancellable: Some(cancellable),
+                        ..CreateOptions::default()
+                    }),
+                );
+            })
+            .assert_ok();
+    }
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+            sc.create_commitment(
+                mb(b"Ship weekly report"),
+                recipient.clone().into(),
+                deadline,
+                OptionalValue::Some(CreateOptions {
+                    cancel_penalty_bps: Some(10_001u64),
+                    ..CreateOptions::default()
+                }),
+            );
+        })
+        .assert_user_error("Cancel penalty exceeds 100%");
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.cancel(2u64);
+        })
+        .assert_user_error("Cancellation disabled");
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(INIT_TS + 1_001));
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.cancel(1u64);
+        })
+        .assert_ok();
+
+    b_wrapper.check_egld_balance(&creator, &rust_biguint!(8 * ONE_EGLD + ONE_EGLD * 3 / 4));
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(10 * ONE_EGLD + ONE_EGLD / 4));
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert_eq!(sc.load_commitment(1).status, CommitmentStatus::Refunded);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn events_describe_each_transition() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+
+    let created = b_wrapper.execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+        sc.create_commitment(
+            mb(b"Ship weekly report"),
+            recipient.clone().into(),
+            deadline,
+            OptionalValue::None,
+        );
+    });
+    created.assert_ok();
+    let log = created.result_logs.last().unwrap();
+    assert_eq!(log.topics[0], b"CommitmentCreated".to_vec());
+    b_wrapper
+        .execute_query(&sc_wrapper, |_sc| {
+            let data = CreatedEventData::<DebugApi>::top_decode(log.data[0].clone()).unwrap();
+            assert_eq!(data.version, EVENT_VERSION);
+            assert_eq!(data.commitment.title, mb(b"Ship weekly report"));
+            assert_eq!(data.commitment.status, CommitmentStatus::Active);
+        })
+        .assert_ok();
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1));
+    let finalized = b_wrapper.execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+        sc.finalize(1u64);
+    });
+    finalized.assert_ok();
+    let log = finalized.result_logs.last().unwrap();
+    assert_eq!(log.topics[0], b"FailedFinalized".to_vec());
+    assert_eq!(u64::top_decode(log.topics[2].clone()).unwrap(), deadline + 1);
+    b_wrapper
+        .execute_query(&sc_wrapper, |_sc| {
+            let data = EventData::<DebugApi>::top_decode(log.data[0].clone()).unwrap();
+            assert_eq!(data.version, EVENT_VERSION);
+            assert_eq!(data.caller, ManagedAddress::<DebugApi>::from_address(&recipient));
+            assert_eq!(data.timestamp, deadline + 1);
+            assert_eq!(data.status, CommitmentStatus::Failed);
+        })
+        .assert_ok();
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1 + 86_400));
+    let claimed = b_wrapper.execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+        sc.claim(1u64);
+    });
+    claimed.assert_ok();
+    let log = claimed
+        .result_logs
+        .iter()
+        .find(|log| log.topics[0] == b"Claimed".to_vec())
+        .unwrap();
+    assert_eq!(log.topics[2], recipient.to_vec());
+    assert_eq!(num_bigint::BigUint::from_bytes_be(&log.topics[3]), rust_biguint!(ONE_EGLD));
+    b_wrapper
+        .execute_query(&sc_wrapper, |_sc| {
+            let data = EventData::<DebugApi>::top_decode(log.data[0].clone()).unwrap();
+            assert_eq!(data.status, CommitmentStatus::Claimed);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn view_pagination_and_batch() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+
+    for i in 0..3u64 {
+        create_default(
             &mut b_wrapper,
             &creator,
             &recipient,
@@ -436,6 +1214,10 @@ fn view_pagination_and_batch() {
             let page = sc.get_ids_page(1u64, 2u64);
             let ids: Vec<u64> = page.into_iter().collect();
             assert_eq!(ids, vec![2u64, 3u64]);
+            let tail: Vec<u64> = sc.get_ids_page(2u64, 10u64).into_iter().collect();
+            assert_eq!(tail, vec![3u64]);
+            assert_eq!(sc.get_ids_page(3u64, 10u64).into_iter().count(), 0usize);
+            assert_eq!(sc.get_ids_page(0u64, 0u64).into_iter().count(), 0usize);
 
             let mut req = MultiValueEncoded::new();
             req.push(1u64);
@@ -449,3 +1231,1086 @@ fn view_pagination_and_batch() {
         })
         .assert_ok();
 }
+
+#[test]
+fn tags_are_validated_and_indexed() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+
+    let create = |b_wrapper: &mut BlockchainStateWrapper, tags: &[&[u8]]| {
+        b_wrapper.execute_tx(&creator, &sc_wrapper, &rust_biguint!(1u64), |sc| {
+            let mut tag_vec = ManagedVec::new();
+            for tag in tags {
+                tag_vec.push(mb(tag));
+            }
+            sc.create_commitment(
+                mb(b"Title"),
+                recipient.clone().into(),
+                INIT_TS + 1_000,
+                OptionalValue::Some(CreateOptions {
+                    tags: Some(tag_vec),
+                    ..CreateOptions::default()
+                }),
+            );
+        })
+    };
+
+    create(&mut b_wrapper, &[b"Fitness"]).assert_user_error("Invalid tag");
+    create(&mut b_wrapper, &[b"-fitness"]).assert_user_error("Invalid tag");
+    create(&mut b_wrapper, &[b"fitness", b"fitness"]).assert_user_error("Duplicate tag");
+    create(&mut b_wrapper, &[b"a", b"b", b"c", b"d", b"e", b"f"])
+        .assert_user_error("Too many tags");
+
+    create(&mut b_wrapper, &[b"fitness", b"open-source"]).assert_ok();
+    create(&mut b_wrapper, &[b"learning"]).assert_ok();
+    create(&mut b_wrapper, &[b"fitness"]).assert_ok();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_curated_tags_only(true);
+            let mut allowed = MultiValueEncoded::new();
+            allowed.push(mb(b"fitness"));
+            allowed.push(mb(b"shipping"));
+            sc.add_allowed_tags(allowed);
+        })
+        .assert_ok();
+
+    create(&mut b_wrapper, &[b"learning"]).assert_user_error("Tag not allowed");
+    create(&mut b_wrapper, &[b"shipping", b"fitness"]).assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let fitness: Vec<u64> = sc.get_ids_by_tag(mb(b"fitness"), 0, 10).into_iter().collect();
+            assert_eq!(fitness, vec![1u64, 3, 4]);
+            let page: Vec<u64> = sc.get_ids_by_tag(mb(b"fitness"), 1, 1).into_iter().collect();
+            assert_eq!(page, vec![3u64]);
+            assert_eq!(sc.get_total_ids_by_tag(mb(b"learning")), 1u64);
+            assert_eq!(sc.get_total_ids_by_tag(mb(b"cooking")), 0u64);
+            assert!(sc.is_curated_tags_only());
+            assert_eq!(sc.get_allowed_tags().len(), 2usize);
+
+            let tags = sc.get_commitment(1u64).tags;
+            assert_eq!(tags.len(), 2usize);
+            assert_eq!(*tags.get(1), mb(b"open-source"));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn description_and_metadata_are_validated_and_stored() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let metadata_hash = [9u8; 32];
+
+    let create = |b_wrapper: &mut BlockchainStateWrapper, description: &[u8], uri: &[u8]| {
+        b_wrapper.execute_tx(&creator, &sc_wrapper, &rust_biguint!(1u64), |sc| {
+            sc.create_commitment(
+                mb(b"Title"),
+                recipient.clone().into(),
+                INIT_TS + 1_000,
+                OptionalValue::Some(CreateOptions {
+                    description: Some(mb(description)),
+                    metadata_uri: Some(mb(uri)),
+                    metadata_hash: Some(ManagedByteArray::new_from_bytes(&metadata_hash)),
+                    ..CreateOptions::default()
+                }),
+            );
+        })
+    };
+
+    let long_description = vec![b'x'; 1_025];
+    create(&mut b_wrapper, &long_description, b"https://example.com/goal.json")
+        .assert_user_error("Description too long");
+    create(&mut b_wrapper, b"Run 42km", b"ftp://example.com/goal.json")
+        .assert_user_error("Invalid metadata URI");
+    create(&mut b_wrapper, b"Run 42km", b"").assert_user_error("Invalid metadata URI length");
+
+    create(
+        &mut b_wrapper,
+        b"Run 42km",
+        b"ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+    )
+    .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.get_commitment(1u64);
+            assert_eq!(c.description, mb(b"Run 42km"));
+            assert_eq!(
+                c.metadata_uri,
+                mb(b"ipfs://QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG")
+            );
+            assert_eq!(c.metadata_hash, mb(&metadata_hash));
+        })
+        .assert_ok();
+}
+
+#[test]
+fn claim_all_aggregates_claimable_commitments() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    for _ in 0..4u64 {
+        create_default(
+            &mut b_wrapper,
+            &creator,
+            &recipient,
+            &sc_wrapper,
+            deadline,
+            ONE_EGLD,
+        );
+    }
+
+    // #2 completes, so it is refunded on finalize and never claimable.
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(2u64, mb(b"https://example.com/proof/2"));
+        })
+        .assert_ok();
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1));
+    for id in 1..=3u64 {
+        b_wrapper
+            .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+                sc.finalize(id);
+            })
+            .assert_ok();
+    }
+
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_all(10u64, OptionalValue::None);
+        })
+        .assert_user_error("Nothing to claim");
+
+    // #4 is finalized later, so its cooldown has not elapsed yet.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 100));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.finalize(4u64);
+        })
+        .assert_ok();
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1 + 86_400));
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_all(1u64, OptionalValue::None);
+        })
+        .assert_ok();
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_all(10u64, OptionalValue::None);
+        })
+        .assert_ok();
+
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(12 * ONE_EGLD));
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert_eq!(sc.load_commitment(1).status, CommitmentStatus::Claimed);
+            assert_eq!(sc.load_commitment(2).status, CommitmentStatus::Refunded);
+            assert_eq!(sc.load_commitment(3).status, CommitmentStatus::Claimed);
+            assert_eq!(sc.load_commitment(4).status, CommitmentStatus::Failed);
+
+            let recipient_address = ManagedAddress::<DebugApi>::from_address(&recipient);
+            let open: Vec<u64> = sc.open_ids_by_recipient(&recipient_address).iter().collect();
+            assert_eq!(open, vec![4u64]);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_all(10u64, OptionalValue::None);
+        })
+        .assert_user_error("Nothing to claim");
+}
+
+#[test]
+fn claim_all_only_scans_limit_open_commitments_from_start() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    // #1 and #2 stay active long after #3 becomes claimable.
+    for _ in 0..2u64 {
+        create_default(
+            &mut b_wrapper,
+            &creator,
+            &recipient,
+            &sc_wrapper,
+            deadline + 1_000_000,
+            1u64,
+        );
+    }
+    create_default(&mut b_wrapper, &creator, &recipient, &sc_wrapper, deadline, ONE_EGLD);
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.finalize(3u64);
+        })
+        .assert_ok();
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1 + 86_400));
+
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_all(2u64, OptionalValue::None);
+        })
+        .assert_user_error("Nothing to claim");
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let recipient_address = ManagedAddress::<DebugApi>::from_address(&recipient);
+            assert_eq!(sc.get_total_open_ids(recipient_address), 3);
+        })
+        .assert_ok();
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_all(2u64, OptionalValue::Some(2u64));
+        })
+        .assert_ok();
+
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(11 * ONE_EGLD));
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert_eq!(sc.load_commitment(3).status, CommitmentStatus::Claimed);
+            assert_eq!(sc.load_commitment(1).status, CommitmentStatus::Active);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn release_to_recipient_pays_users_and_contracts() {
+    use mock_recipient::MockRecipient;
+
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    let recipient_wrapper = b_wrapper.create_sc_account(
+        &rust_biguint!(0),
+        Some(&creator),
+        mock_recipient::contract_obj,
+        "mock-recipient.wasm",
+    );
+    let contract_recipient = recipient_wrapper.address_ref().clone();
+
+    create_default(
+        &mut b_wrapper,
+        &creator,
+        &recipient,
+        &sc_wrapper,
+        deadline,
+        ONE_EGLD,
+    );
+    for _ in 0..2 {
+        create_default(
+            &mut b_wrapper,
+            &creator,
+            &contract_recipient,
+            &sc_wrapper,
+            deadline,
+            ONE_EGLD,
+        );
+    }
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 86_399));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.release_to_recipient(1u64);
+        })
+        .assert_user_error("Cooldown not reached");
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 86_400));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.release_to_recipient(1u64);
+        })
+        .assert_ok();
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(11 * ONE_EGLD));
+
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_release_endpoint(OptionalValue::Some(mb(b"on_release")));
+        })
+        .assert_user_error("Only contracts can set a release endpoint");
+    b_wrapper
+        .execute_tx(&contract_recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_release_endpoint(OptionalValue::Some(mb(b"")));
+        })
+        .assert_user_error("Invalid endpoint name");
+    b_wrapper
+        .execute_tx(&contract_recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_release_endpoint(OptionalValue::Some(mb(b"on_release")));
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.release_to_recipient(2u64);
+        })
+        .assert_ok();
+    b_wrapper.check_egld_balance(&contract_recipient, &rust_biguint!(ONE_EGLD));
+    b_wrapper
+        .execute_query(&recipient_wrapper, |sc| {
+            assert_eq!(sc.received(2u64).get(), ONE_EGLD);
+        })
+        .assert_ok();
+
+    // A rejected payment comes back and leaves the stake claimable.
+    b_wrapper
+        .execute_tx(&creator, &recipient_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_rejecting(true);
+        })
+        .assert_ok();
+    let rejected = b_wrapper.execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+        sc.release_to_recipient(3u64);
+    });
+    rejected.assert_ok();
+    let requested = rejected
+        .result_logs
+        .iter()
+        .find(|log| log.topics[0] == b"ReleaseRequested".to_vec())
+        .unwrap();
+    assert_eq!(u64::top_decode(requested.topics[1].clone()).unwrap(), 3);
+    assert!(rejected
+        .result_logs
+        .iter()
+        .any(|log| log.topics[0] == b"ReleaseFailed".to_vec()));
+    // Dated to when it lapsed, in storage and in the event alike.
+    let failed = rejected
+        .result_logs
+        .iter()
+        .find(|log| log.topics[0] == b"FailedFinalized".to_vec())
+        .unwrap();
+    assert_eq!(u64::top_decode(failed.topics[2].clone()).unwrap(), deadline);
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.load_commitment(3);
+            assert_eq!(c.status, CommitmentStatus::Failed);
+            assert_eq!(c.finalized_at, deadline);
+        })
+        .assert_ok();
+    b_wrapper.check_egld_balance(&contract_recipient, &rust_biguint!(ONE_EGLD));
+    b_wrapper.check_egld_balance(sc_wrapper.address_ref(), &rust_biguint!(ONE_EGLD));
+
+    b_wrapper
+        .execute_tx(&creator, &recipient_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_rejecting(false);
+        })
+        .assert_ok();
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.release_to_recipient(3u64);
+        })
+        .assert_ok();
+    b_wrapper.check_egld_balance(&contract_recipient, &rust_biguint!(2 * ONE_EGLD));
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            for id in 1..=3u64 {
+                assert_eq!(sc.load_commitment(id).status, CommitmentStatus::Claimed);
+            }
+            assert_eq!(
+                sc.get_release_endpoint(contract_recipient.clone().into()),
+                mb(b"on_release")
+            );
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.release_to_recipient(3u64);
+        })
+        .assert_user_error("Commitment is not failed");
+}
+
+#[test]
+fn delegates_claim_and_submit_proofs_for_their_owner() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    for _ in 0..2 {
+        create_default(
+            &mut b_wrapper,
+            &creator,
+            &recipient,
+            &sc_wrapper,
+            deadline,
+            ONE_EGLD,
+        );
+    }
+
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(1u64, mb(b"https://example.com/proof"));
+        })
+        .assert_user_error("Only creator can submit proof");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.add_delegate(DelegationKind::Proof, creator.clone().into());
+        })
+        .assert_user_error("Cannot delegate to self");
+
+    let added = b_wrapper.execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+        sc.add_delegate(DelegationKind::Proof, stranger.clone().into());
+    });
+    added.assert_ok();
+    let log = added.result_logs.last().unwrap();
+    assert_eq!(log.topics[0], b"DelegateAdded".to_vec());
+    assert_eq!(log.topics[1], creator.to_vec());
+    assert_eq!(log.topics[3], stranger.to_vec());
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.add_delegate(DelegationKind::Proof, stranger.clone().into());
+        })
+        .assert_user_error("Already a delegate");
+
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(1u64, mb(b"https://example.com/proof"));
+        })
+        .assert_ok();
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert_eq!(sc.load_commitment(1).status, CommitmentStatus::Completed);
+            let delegates: Vec<_> = sc
+                .get_delegates(creator.clone().into(), DelegationKind::Proof)
+                .into_iter()
+                .collect();
+            assert_eq!(delegates, vec![ManagedAddress::from(stranger.clone())]);
+            assert!(!sc.is_delegate(
+                creator.clone().into(),
+                DelegationKind::Claim,
+                stranger.clone().into()
+            ));
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.remove_delegate(DelegationKind::Proof, stranger.clone().into());
+        })
+        .assert_ok();
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.remove_delegate(DelegationKind::Proof, stranger.clone().into());
+        })
+        .assert_user_error("Not a delegate");
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(2u64, mb(b"https://example.com/proof"));
+        })
+        .assert_user_error("Only creator can submit proof");
+
+    // A claim delegate triggers the claim; the stake still goes to the recipient.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 86_400));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(2u64);
+        })
+        .assert_user_error("Only recipient can claim");
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.add_delegate(DelegationKind::Claim, stranger.clone().into());
+        })
+        .assert_ok();
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(2u64);
+        })
+        .assert_ok();
+
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(11 * ONE_EGLD));
+    b_wrapper.check_egld_balance(&stranger, &rust_biguint!(10 * ONE_EGLD));
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert_eq!(sc.load_commitment(2).status, CommitmentStatus::Claimed);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn settled_commitments_are_pruned_after_retention() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    for _ in 0..3 {
+        b_wrapper
+            .execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+                let mut tags = ManagedVec::new();
+                tags.push(mb(b"fitness"));
+                sc.create_commitment(
+                    mb(b"Run 5k"),
+                    recipient.clone().into(),
+                    deadline,
+                    OptionalValue::Some(CreateOptions {
+                        tags: Some(tags),
+                        ..CreateOptions::default()
+                    }),
+                );
+            })
+            .assert_ok();
+    }
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(1u64, mb(b"https://example.com/proof"));
+        })
+        .assert_ok();
+
+    let settled_at = deadline + 1;
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(settled_at));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.finalize(1u64);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            assert_eq!(sc.get_archive_retention_seconds(), 180 * 86_400);
+            sc.set_archive_retention_seconds(1_000);
+        })
+        .assert_ok();
+
+    let prune = |b_wrapper: &mut BlockchainStateWrapper| {
+        b_wrapper.execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            let mut ids = MultiValueEncoded::new();
+            for id in [1u64, 2, 99] {
+                ids.push(id);
+            }
+            sc.prune_commitments(ids);
+        })
+    };
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(settled_at + 999));
+    prune(&mut b_wrapper).assert_ok();
+    let mut record_hash = Vec::new();
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert!(!sc.is_pruned(1u64));
+            assert_eq!(sc.get_proof_history(1u64).len(), 1);
+            let encoded = sc.serializer().top_encode_to_managed_buffer(&sc.get_commitment(1u64));
+            record_hash = sc.crypto().sha256(&encoded).to_byte_array().to_vec();
+        })
+        .assert_ok();
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(settled_at + 1_000));
+    let pruned = prune(&mut b_wrapper);
+    pruned.assert_ok();
+    let logs: Vec<_> = pruned
+        .result_logs
+        .iter()
+        .filter(|log| log.topics[0] == b"CommitmentPruned".to_vec())
+        .collect();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].topics[1], 1u64.to_be_bytes()[7..].to_vec());
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert!(sc.is_pruned(1u64));
+            assert!(!sc.is_pruned(2u64));
+            let OptionalValue::Some(archived) = sc.get_archived_commitment(1u64) else {
+                panic!("expected an archived record");
+            };
+            assert_eq!(archived.status, CommitmentStatus::Refunded);
+            assert_eq!(archived.settled_at, settled_at);
+            assert_eq!(archived.amount, ONE_EGLD);
+            assert_eq!(archived.record_hash.to_boxed_bytes().as_slice(), record_hash.as_slice());
+            assert!(sc.get_archived_commitment(2u64).is_none());
+            assert!(sc.proof_history(1).is_empty());
+
+            // The newest id takes the pruned one's place in the tag index.
+            let fitness: Vec<u64> = sc.get_ids_by_tag(mb(b"fitness"), 0, 10).into_iter().collect();
+            assert_eq!(fitness, vec![3u64, 2]);
+
+            let mut req = MultiValueEncoded::new();
+            req.push(1u64);
+            req.push(2u64);
+            let list: Vec<_> = sc.get_commitments_batch(req).into_iter().collect();
+            assert_eq!(list.len(), 1);
+            assert_eq!(list[0].id, 2u64);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            sc.get_commitment(1u64);
+        })
+        .assert_user_error("Commitment pruned");
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            sc.get_commitment(99u64);
+        })
+        .assert_user_error("Commitment not found");
+}
+
+#[test]
+fn legacy_records_decode_and_migrate() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+
+    // Records written by the first deployment have no layout version.
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            for id in 1..=2u64 {
+                sc.legacy_commitments(id).set(CommitmentV0 {
+                    id,
+                    creator: creator.clone().into(),
+                    recipient: recipient.clone().into(),
+                    amount: BigUint::from(ONE_EGLD),
+                    deadline,
+                    cooldown_seconds: 86_400,
+                    created_at: INIT_TS,
+                    status: CommitmentStatus::Active,
+                    title: mb(b"Legacy"),
+                    proof_url: ManagedBuffer::new(),
+                    proof_hash: ManagedBuffer::new(),
+                    proof_submitted_at: 0,
+                    finalized_at: 0,
+                });
+            }
+            sc.next_id().set(3u64);
+        })
+        .assert_ok();
+    b_wrapper.set_egld_balance(sc_wrapper.address_ref(), &rust_biguint!(2 * ONE_EGLD));
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.upgrade();
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.get_commitment(1u64);
+            assert_eq!(c.title, mb(b"Legacy"));
+            assert_eq!(c.fallback, ManagedAddress::<DebugApi>::from_address(&creator));
+            assert_eq!(c.forgiven_amount, BigUint::zero());
+            assert_eq!(sc.commitment_version(1).get(), 0u8);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            let mut ids = MultiValueEncoded::new();
+            ids.push(1u64);
+            ids.push(99u64);
+            sc.migrate_commitments(ids);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert_eq!(sc.commitment_version(1).get(), STORAGE_VERSION);
+            assert_eq!(sc.commitment_version(2).get(), 0u8);
+            assert_eq!(sc.load_commitment(1).title, mb(b"Legacy"));
+
+            let recipient_address = ManagedAddress::<DebugApi>::from_address(&recipient);
+            assert!(sc.open_ids_by_recipient(&recipient_address).contains(&1u64));
+        })
+        .assert_ok();
+
+    // Unmigrated records still settle, and are rewritten on the way.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 86_400));
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_all(10u64, OptionalValue::None);
+            sc.claim(2u64);
+        })
+        .assert_ok();
+
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(12 * ONE_EGLD));
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert_eq!(sc.load_commitment(1).status, CommitmentStatus::Claimed);
+            assert_eq!(sc.load_commitment(2).status, CommitmentStatus::Claimed);
+            assert_eq!(sc.commitment_version(2).get(), STORAGE_VERSION);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn legacy_failed_stakes_never_expire() {
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.legacy_commitments(1).set(CommitmentV0 {
+                id: 1,
+                creator: creator.clone().into(),
+                recipient: recipient.clone().into(),
+                amount: BigUint::from(ONE_EGLD),
+                deadline,
+                cooldown_seconds: 86_400,
+                created_at: INIT_TS,
+                status: CommitmentStatus::Failed,
+                title: mb(b"Legacy"),
+                proof_url: ManagedBuffer::new(),
+                proof_hash: ManagedBuffer::new(),
+                proof_submitted_at: 0,
+                finalized_at: deadline + 1,
+            });
+            sc.next_id().set(2u64);
+        })
+        .assert_ok();
+    b_wrapper.set_egld_balance(sc_wrapper.address_ref(), &rust_biguint!(ONE_EGLD));
+
+    // The first deployment had no claim window, so the stake cannot be swept.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 10 * 365 * 86_400));
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.sweep(1u64);
+        })
+        .assert_user_error("Claim window not expired");
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim(1u64);
+        })
+        .assert_ok();
+
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(11 * ONE_EGLD));
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Claimed);
+            assert_eq!(c.claim_window_seconds, u64::MAX);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn records_touched_before_migration_are_indexed_for_claim_all() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.legacy_commitments(1).set(CommitmentV0 {
+                id: 1,
+                creator: creator.clone().into(),
+                recipient: recipient.clone().into(),
+                amount: BigUint::from(ONE_EGLD),
+                deadline,
+                cooldown_seconds: 86_400,
+                created_at: INIT_TS,
+                status: CommitmentStatus::Active,
+                title: mb(b"Legacy"),
+                proof_url: ManagedBuffer::new(),
+                proof_hash: ManagedBuffer::new(),
+                proof_submitted_at: 0,
+                finalized_at: 0,
+            });
+            sc.next_id().set(2u64);
+        })
+        .assert_ok();
+    b_wrapper.set_egld_balance(sc_wrapper.address_ref(), &rust_biguint!(ONE_EGLD));
+
+    // `finalize` moves the record to the current layout before any
+    // `migrate_commitments`, which then skips it.
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1));
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.finalize(1u64);
+        })
+        .assert_ok();
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            let mut ids = MultiValueEncoded::new();
+            ids.push(1u64);
+            sc.migrate_commitments(ids);
+        })
+        .assert_ok();
+
+    b_wrapper.set_block_timestamp_seconds(TimestampSeconds::new(deadline + 1 + 86_400));
+    b_wrapper
+        .execute_tx(&recipient, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.claim_all(10u64, OptionalValue::None);
+        })
+        .assert_ok();
+
+    b_wrapper.check_egld_balance(&recipient, &rust_biguint!(11 * ONE_EGLD));
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            assert_eq!(sc.get_commitment(1u64).status, CommitmentStatus::Claimed);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn layout_v1_records_move_to_split_layout() {
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+
+    create_default(&mut b_wrapper, &creator, &recipient, &sc_wrapper, deadline, ONE_EGLD);
+    create_default(&mut b_wrapper, &creator, &recipient, &sc_wrapper, deadline, ONE_EGLD);
+
+    // Rewrite both as whole records under `commitments`, as layout 1 stored them.
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            for id in 1..=2u64 {
+                let commitment = sc.load_commitment(id);
+                sc.commitment_terms(id).clear();
+                sc.commitment_metadata(id).clear();
+                sc.commitment_state(id).clear();
+                sc.commitments(id).set(&commitment);
+                sc.commitment_version(id).set(1u8);
+            }
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.get_commitment(1u64);
+            assert_eq!(c.title, mb(b"Ship weekly report"));
+            assert_eq!(c.status, CommitmentStatus::Active);
+            assert_eq!(sc.get_total_ids(), 2u64);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            let mut ids = MultiValueEncoded::new();
+            ids.push(1u64);
+            sc.migrate_commitments(ids);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(2u64, mb(b"https://example.com/report"));
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            for id in 1..=2u64 {
+                assert_eq!(sc.commitment_version(id).get(), STORAGE_VERSION);
+                assert!(sc.commitments(id).is_empty());
+                assert_eq!(sc.get_commitment(id).title, mb(b"Ship weekly report"));
+            }
+            assert!(sc.commitment_proof(1).is_empty());
+            assert_eq!(sc.get_commitment(1u64).proof_url, ManagedBuffer::new());
+
+            let proved = sc.get_commitment(2u64);
+            assert_eq!(proved.status, CommitmentStatus::Completed);
+            assert_eq!(proved.proof_url, mb(b"https://example.com/report"));
+            assert_eq!(sc.commitment_state(2).get().status, CommitmentStatus::Completed);
+        })
+        .assert_ok();
+}
+
+#[test]
+fn status_enum_keeps_legacy_u8_encoding() {
+    let statuses = [
+        CommitmentStatus::Active,
+        CommitmentStatus::Completed,
+        CommitmentStatus::Failed,
+        CommitmentStatus::Refunded,
+        CommitmentStatus::Claimed,
+    ];
+
+    for (code, status) in statuses.into_iter().enumerate() {
+        let code = code as u8;
+        assert_eq!(
+            multiversx_sc::codec::dep_encode_to_vec(&status).unwrap(),
+            multiversx_sc::codec::dep_encode_to_vec(&code).unwrap()
+        );
+        assert_eq!(
+            multiversx_sc::codec::top_encode_to_vec_u8_or_panic(&status),
+            multiversx_sc::codec::top_encode_to_vec_u8_or_panic(&code)
+        );
+    }
+}
+
+#[test]
+fn oracle_verification_completes_or_fails_commitment() {
+    use mock_oracle::MockOracle;
+
+    let (mut b_wrapper, creator, recipient, stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    let oracle_wrapper = b_wrapper.create_sc_account(
+        &rust_biguint!(0),
+        Some(&creator),
+        mock_oracle::contract_obj,
+        "mock-oracle.wasm",
+    );
+    let oracle_address = oracle_wrapper.address_ref().clone();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+            sc.create_commitment(
+                mb(b"Title"),
+                recipient.clone().into(),
+                deadline,
+                OptionalValue::Some(CreateOptions {
+                    oracle: Some(stranger.clone().into()),
+                    ..CreateOptions::default()
+                }),
+            );
+        })
+        .assert_user_error("Oracle must be a smart contract");
+
+    for _ in 0..2u64 {
+        b_wrapper
+            .execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+                sc.create_commitment(
+                    mb(b"Merge the PR"),
+                    recipient.clone().into(),
+                    deadline,
+                    OptionalValue::Some(CreateOptions {
+                        oracle: Some(oracle_address.clone().into()),
+                        ..CreateOptions::default()
+                    }),
+                );
+            })
+            .assert_ok();
+    }
+    create_default(
+        &mut b_wrapper,
+        &creator,
+        &recipient,
+        &sc_wrapper,
+        deadline,
+        ONE_EGLD,
+    );
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.request_oracle_verification(3u64);
+        })
+        .assert_user_error("No oracle configured");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(1u64, mb(b"https://example.com/merged"));
+        })
+        .assert_user_error("Oracle verification required");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_sealed_proof(1u64, ManagedByteArray::new_from_bytes(&[7u8; 32]));
+        })
+        .assert_user_error("Oracle verification required");
+    b_wrapper
+        .execute_tx(&stranger, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.request_oracle_verification(1u64);
+        })
+        .assert_user_error("Only creator can request verification");
+
+    b_wrapper
+        .execute_tx(&creator, &oracle_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.set_verdict(1u64, true);
+            sc.set_verdict(2u64, false);
+        })
+        .assert_ok();
+
+    for id in 1..=2u64 {
+        b_wrapper
+            .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+                sc.request_oracle_verification(id);
+            })
+            .assert_ok();
+    }
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let verified = sc.load_commitment(1);
+            assert_eq!(verified.status, CommitmentStatus::Completed);
+            assert_eq!(verified.proof_kind, ProofKind::OracleAttestation);
+            assert_eq!(verified.proof_submitted_at, INIT_TS);
+
+            let rejected = sc.load_commitment(2);
+            assert_eq!(rejected.status, CommitmentStatus::Failed);
+            assert_eq!(rejected.finalized_at, INIT_TS);
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(1u64, mb(b"https://example.com/merged"));
+        })
+        .assert_user_error("Verified proof cannot be revised");
+}
+
+#[test]
+fn attested_proof_requires_valid_attester_signature() {
+    use ed25519_dalek::{Signer, SigningKey};
+
+    let (mut b_wrapper, creator, recipient, _stranger, sc_wrapper) = setup();
+    let deadline = INIT_TS + 700;
+    let attester_key = SigningKey::from_bytes(&[42u8; 32]);
+    let impostor_key = SigningKey::from_bytes(&[43u8; 32]);
+    let attester = Address::from(attester_key.verifying_key().to_bytes());
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(ONE_EGLD), |sc| {
+            sc.create_commitment(
+                mb(b"Pass the exam"),
+                recipient.clone().into(),
+                deadline,
+                OptionalValue::Some(CreateOptions {
+                    attester: Some(attester.clone().into()),
+                    ..CreateOptions::default()
+                }),
+            );
+        })
+        .assert_ok();
+
+    let proof_url: &[u8] = b"https://example.com/certificate";
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_proof(1u64, mb(proof_url));
+        })
+        .assert_user_error("Attested proof required");
+
+    let mut message = Vec::new();
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            message = sc
+                .get_attestation_message(1u64, mb(proof_url))
+                .to_boxed_bytes()
+                .into_vec();
+        })
+        .assert_ok();
+    assert_eq!(&message[..32], sc_wrapper.address_ref().as_bytes());
+    assert_eq!(&message[32..40], &1u64.to_be_bytes());
+
+    let forged = impostor_key.sign(&message).to_bytes();
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_attested_proof(
+                1u64,
+                mb(proof_url),
+                ManagedByteArray::new_from_bytes(&forged),
+            );
+        })
+        .assert_error(10, "ed25519 verify error");
+
+    let signature = attester_key.sign(&message).to_bytes();
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_attested_proof(
+                1u64,
+                mb(b"https://example.com/other"),
+                ManagedByteArray::new_from_bytes(&signature),
+            );
+        })
+        .assert_error(10, "ed25519 verify error");
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_attested_proof(
+                1u64,
+                mb(proof_url),
+                ManagedByteArray::new_from_bytes(&signature),
+            );
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_query(&sc_wrapper, |sc| {
+            let c = sc.load_commitment(1);
+            assert_eq!(c.status, CommitmentStatus::Completed);
+            assert_eq!(c.proof_kind, ProofKind::AttestedUrl);
+            assert_eq!(c.proof_url, mb(proof_url));
+        })
+        .assert_ok();
+
+    b_wrapper
+        .execute_tx(&creator, &sc_wrapper, &rust_biguint!(0u64), |sc| {
+            sc.submit_attested_proof(
+                1u64,
+                mb(proof_url),
+                ManagedByteArray::new_from_bytes(&signature),
+            );
+        })
+        .assert_user_error("Verified proof cannot be revised");
+}
@@ -0,0 +1,113 @@
+//! `create_commitment` called with its arguments encoded as a transaction
+//! carries them, rather than through the Rust method.
+
+use multiversx_sc_scenario::imports::*;
+use public_commitment_fund::{Commitment, CommitmentStatus, CreateOptions};
+
+const OWNER: TestAddress = TestAddress::new("owner");
+const CREATOR: TestAddress = TestAddress::new("creator");
+const RECIPIENT: TestAddress = TestAddress::new("recipient");
+const FUND: TestSCAddress = TestSCAddress::new("fund");
+const CODE_PATH: MxscPath = MxscPath::new("output/public-commitment-fund.mxsc.json");
+
+const INIT_TS: u64 = 1_000;
+const DEADLINE: u64 = INIT_TS + 10_000;
+const STAKE: u64 = 1_000;
+
+fn world() -> ScenarioWorld {
+    let mut world = ScenarioWorld::new();
+    world.register_contract(CODE_PATH, public_commitment_fund::ContractBuilder);
+    world.account(OWNER).nonce(1);
+    world.account(CREATOR).nonce(1).balance(10 * STAKE);
+    world.account(RECIPIENT).nonce(1);
+    world.current_block().block_timestamp_seconds(INIT_TS);
+
+    world
+        .tx()
+        .from(OWNER)
+        .raw_deploy()
+        .code(CODE_PATH)
+        .new_address(FUND)
+        .run();
+    world
+}
+
+fn get_commitment(world: &mut ScenarioWorld, id: u64) -> Commitment<StaticApi> {
+    world
+        .query()
+        .to(FUND)
+        .raw_call("get_commitment")
+        .argument(&id)
+        .original_result::<Commitment<StaticApi>>()
+        .returns(ReturnsResult)
+        .run()
+}
+
+#[test]
+fn later_options_are_set_without_earlier_ones() {
+    let mut world = world();
+
+    // No options at all: only the three required arguments are sent.
+    world
+        .tx()
+        .from(CREATOR)
+        .to(FUND)
+        .egld(STAKE)
+        .raw_call("create_commitment")
+        .argument(&"Plain")
+        .argument(&RECIPIENT.to_address())
+        .argument(&DEADLINE)
+        .run();
+
+    let mut tags = ManagedVec::<StaticApi, ManagedBuffer<StaticApi>>::new();
+    tags.push(ManagedBuffer::from("running"));
+    let options = CreateOptions::<StaticApi> {
+        start_at: Some(INIT_TS + 500),
+        tags: Some(tags.clone()),
+        description: Some(ManagedBuffer::from("Sub-4h marathon")),
+        ..CreateOptions::default()
+    };
+    world
+        .tx()
+        .from(CREATOR)
+        .to(FUND)
+        .egld(STAKE)
+        .raw_call("create_commitment")
+        .argument(&"Marathon")
+        .argument(&RECIPIENT.to_address())
+        .argument(&DEADLINE)
+        .argument(&options)
+        .run();
+
+    let plain = get_commitment(&mut world, 1);
+    assert_eq!(plain.start_at, INIT_TS);
+    assert!(plain.tags.is_empty());
+
+    let marathon = get_commitment(&mut world, 2);
+    assert_eq!(marathon.status, CommitmentStatus::Active);
+    assert_eq!(marathon.start_at, INIT_TS + 500);
+    assert_eq!(marathon.tags, tags);
+    assert_eq!(marathon.description, ManagedBuffer::from("Sub-4h marathon"));
+    assert!(marathon.oracle.is_zero());
+    assert!(marathon.attester.is_zero());
+    assert_eq!(marathon.fallback, CREATOR.to_managed_address());
+    assert_eq!(marathon.cooldown_seconds, 86_400);
+    assert!(marathon.cancellable);
+
+    let half_metadata = CreateOptions::<StaticApi> {
+        metadata_uri: Some(ManagedBuffer::from("ipfs://criteria.json")),
+        ..CreateOptions::default()
+    };
+    world
+        .tx()
+        .from(CREATOR)
+        .to(FUND)
+        .egld(STAKE)
+        .raw_call("create_commitment")
+        .argument(&"Half")
+        .argument(&RECIPIENT.to_address())
+        .argument(&DEADLINE)
+        .argument(&half_metadata)
+        .returns(ExpectError(4, "Metadata URI and hash go together"))
+        .run();
+}
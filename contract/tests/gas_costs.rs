@@ -0,0 +1,119 @@
+//! Gas used by the main endpoints, measured on the compiled contract with the
+//! V8 gas schedule. Needs `cargo run -- build` in `meta` first, then
+//! `cargo test --features gas-tests --test gas_costs -- --nocapture`.
+#![cfg(feature = "gas-tests")]
+
+use multiversx_sc_scenario::imports::*;
+
+const OWNER: TestAddress = TestAddress::new("owner");
+const CREATOR: TestAddress = TestAddress::new("creator");
+const RECIPIENT: TestAddress = TestAddress::new("recipient");
+const FUND: TestSCAddress = TestSCAddress::new("fund");
+const CODE_PATH: MxscPath = MxscPath::new("output/public-commitment-fund.mxsc.json");
+
+const INIT_TS: u64 = 1_000;
+const DEADLINE: u64 = INIT_TS + 700;
+const COOLDOWN_SECONDS: u64 = 86_400;
+const ONE_EGLD: u64 = 1_000_000_000_000_000_000;
+const GAS_LIMIT: u64 = 100_000_000;
+const GAS_MARGIN_PERCENT: u64 = 2;
+
+fn world() -> ScenarioWorld {
+    let mut world = ScenarioWorld::new()
+        .executor_config(ExecutorConfig::Experimental)
+        .gas_schedule(GasScheduleVersion::V8);
+    world.account(OWNER).nonce(1);
+    world.account(CREATOR).nonce(1).balance(5 * ONE_EGLD);
+    world.account(RECIPIENT).nonce(1);
+    world.current_block().block_timestamp_seconds(INIT_TS);
+
+    world
+        .tx()
+        .from(OWNER)
+        .gas(GAS_LIMIT)
+        .raw_deploy()
+        .code(CODE_PATH)
+        .new_address(FUND)
+        .run();
+    world
+}
+
+fn call(world: &mut ScenarioWorld, from: TestAddress, egld: u64, endpoint: &str, id: u64) -> u64 {
+    world
+        .tx()
+        .from(from)
+        .to(FUND)
+        .gas(GAS_LIMIT)
+        .egld(egld)
+        .raw_call(endpoint)
+        .argument(&id)
+        .returns(ReturnsGasUsed)
+        .run()
+}
+
+fn create(world: &mut ScenarioWorld) -> u64 {
+    world
+        .tx()
+        .from(CREATOR)
+        .to(FUND)
+        .gas(GAS_LIMIT)
+        .egld(ONE_EGLD)
+        .raw_call("create_commitment")
+        .argument(&"Run a marathon before the end of the season")
+        .argument(&RECIPIENT.to_address())
+        .argument(&DEADLINE)
+        .returns(ReturnsGasUsed)
+        .run()
+}
+
+#[test]
+fn gas_used_per_endpoint() {
+    let mut world = world();
+
+    let created = create(&mut world);
+    create(&mut world);
+    create(&mut world);
+
+    let proof_url = "https://example.com/results/2025/city-marathon/finishers?bib=4821&format=full";
+    let proved = world
+        .tx()
+        .from(CREATOR)
+        .to(FUND)
+        .gas(GAS_LIMIT)
+        .raw_call("submit_proof")
+        .argument(&2u64)
+        .argument(&proof_url)
+        .returns(ReturnsGasUsed)
+        .run();
+    let cancelled = call(&mut world, CREATOR, 0, "cancel", 3);
+
+    world.current_block().block_timestamp_seconds(DEADLINE + 1);
+    let failed = call(&mut world, RECIPIENT, 0, "finalize", 1);
+    let refunded = call(&mut world, RECIPIENT, 0, "finalize", 2);
+
+    world
+        .current_block()
+        .block_timestamp_seconds(DEADLINE + 1 + COOLDOWN_SECONDS);
+    let claimed = call(&mut world, RECIPIENT, 0, "claim", 1);
+
+    // Gas used with each commitment stored as one record (layout 1), and as
+    // measured with terms, metadata, state and proof in separate mappers
+    // (layout 2). Every endpoint must stay cheaper than with layout 1 and
+    // within `GAS_MARGIN_PERCENT` of its layout 2 figure.
+    for (endpoint, gas, layout_1, layout_2) in [
+        ("create_commitment", created, 1_426_427, 1_408_217),
+        ("submit_proof", proved, 1_946_730, 1_826_735),
+        ("cancel", cancelled, 1_238_075, 1_016_020),
+        ("finalize (failed)", failed, 634_239, 419_654),
+        ("finalize (refunded)", refunded, 1_227_035, 1_004_980),
+        ("claim", claimed, 1_241_705, 1_019_660),
+    ] {
+        println!("{endpoint:<20} {gas:>10} (layout 1: {layout_1}, layout 2: {layout_2})");
+        assert!(gas < layout_1, "{endpoint} used {gas} gas, no less than layout 1's {layout_1}");
+        let margin = layout_2 * GAS_MARGIN_PERCENT / 100;
+        assert!(
+            gas.abs_diff(layout_2) <= margin,
+            "{endpoint} used {gas} gas, more than {GAS_MARGIN_PERCENT}% away from {layout_2}"
+        );
+    }
+}
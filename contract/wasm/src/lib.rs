@@ -5,9 +5,10 @@
 ////////////////////////////////////////////////////
 
 // Init:                                 1
-// Endpoints:                            9
-// Async Callback (empty):               1
-// Total number of exported functions:  11
+// Upgrade:                              1
+// Endpoints:                           41
+// Async Callback:                       1
+// Total number of exported functions:  44
 
 #![no_std]
 
@@ -18,16 +19,49 @@ multiversx_sc_wasm_adapter::endpoints! {
     public_commitment_fund
     (
         init => init
+        upgrade => upgrade
+        migrate_commitments => migrate_commitments
+        set_archive_retention_seconds => set_archive_retention_seconds
+        prune_commitments => prune_commitments
+        set_curated_tags_only => set_curated_tags_only
+        add_allowed_tags => add_allowed_tags
+        remove_allowed_tags => remove_allowed_tags
         create_commitment => create_commitment
         submit_proof => submit_proof
+        submit_typed_proof => submit_typed_proof
+        submit_attested_proof => submit_attested_proof
+        request_oracle_verification => request_oracle_verification
+        submit_sealed_proof => submit_sealed_proof
+        reveal_proof => reveal_proof
         finalize => finalize
         claim => claim
+        claim_all => claim_all
+        release_to_recipient => release_to_recipient
+        set_release_endpoint => set_release_endpoint
+        sweep => sweep
+        forgive => forgive
         cancel => cancel
+        concede => concede
+        get_archived_commitment => get_archived_commitment
+        is_pruned => is_pruned
+        get_archive_retention_seconds => get_archive_retention_seconds
         get_commitment => get_commitment
+        get_proof_history => get_proof_history
+        get_attestation_message => get_attestation_message
+        get_release_endpoint => get_release_endpoint
         get_total_ids => get_total_ids
         get_ids_page => get_ids_page
+        get_total_open_ids => get_total_open_ids
+        get_ids_by_tag => get_ids_by_tag
+        get_total_ids_by_tag => get_total_ids_by_tag
+        is_curated_tags_only => is_curated_tags_only
+        get_allowed_tags => get_allowed_tags
         get_commitments_batch => get_commitments_batch
+        add_delegate => add_delegate
+        remove_delegate => remove_delegate
+        get_delegates => get_delegates
+        is_delegate => is_delegate
     )
 }
 
-multiversx_sc_wasm_adapter::async_callback_empty! {}
+multiversx_sc_wasm_adapter::async_callback! { public_commitment_fund }
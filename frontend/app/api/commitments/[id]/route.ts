@@ -32,14 +32,20 @@ export async function GET(
   } catch (err) {
     const message = err instanceof Error ? err.message : String(err);
     const notFound = message.toLowerCase().includes('not found');
+    // Settled commitments past their retention may have been pruned onchain.
+    const pruned = message.toLowerCase().includes('pruned');
 
     return NextResponse.json(
       {
-        error: notFound ? 'Commitment not found' : 'Failed to query commitment',
+        error: pruned
+          ? 'Commitment pruned'
+          : notFound
+            ? 'Commitment not found'
+            : 'Failed to query commitment',
         details: message,
       },
       {
-        status: notFound ? 404 : 502,
+        status: pruned ? 410 : notFound ? 404 : 502,
         headers: {
           'Cache-Control': 'no-store',
         },
@@ -16,11 +16,13 @@ import {
   buildCancelPayload,
   buildClaimPayload,
   DappTransactionPayload,
+  buildForgivePayload,
   buildFinalizePayload,
   buildSubmitProofPayload,
+  buildSweepPayload,
 } from '@/lib/tx';
 import { formatDateTime, shortAddress, weiToEgld } from '@/lib/format';
-import { Commitment, CommitmentStatus } from '@/types';
+import { Commitment, CommitmentStatus, ProofKind } from '@/types';
 
 interface DetailResponse {
   item: Commitment;
@@ -83,7 +85,10 @@ export default function CommitmentDetailPage() {
         canSubmitProof: false,
         canFinalize: false,
         canClaim: false,
+        canForgive: false,
+        canSweep: false,
         canCancel: false,
+        cancelPenaltyBps: 0,
       };
     }
 
@@ -187,6 +192,42 @@ export default function CommitmentDetailPage() {
     );
   };
 
+  const onForgive = async () => {
+    if (!item) return;
+
+    await runAction(
+      () =>
+        buildForgivePayload({
+          sender: address || '',
+          chainId: network.chainId,
+          id: item.id,
+        }),
+      {
+        processing: 'Forgiving stake...',
+        success: 'Stake refunded to creator.',
+        error: 'Forgive failed.',
+      }
+    );
+  };
+
+  const onSweep = async () => {
+    if (!item) return;
+
+    await runAction(
+      () =>
+        buildSweepPayload({
+          sender: address || '',
+          chainId: network.chainId,
+          id: item.id,
+        }),
+      {
+        processing: 'Sweeping unclaimed funds...',
+        success: 'Funds sent to fallback address.',
+        error: 'Sweep failed.',
+      }
+    );
+  };
+
   const onCancel = async () => {
     if (!item) return;
 
@@ -229,6 +270,10 @@ export default function CommitmentDetailPage() {
           </div>
 
           <p className="commitment-card__amount">{weiToEgld(item.amount, 6)} EGLD</p>
+          {item.tags.length > 0 ? (
+            <p className="inline-note">{item.tags.map((tag) => `#${tag}`).join(' ')}</p>
+          ) : null}
+          {item.description ? <p>{item.description}</p> : null}
 
           <div className="form-grid">
             <div>
@@ -241,6 +286,10 @@ export default function CommitmentDetailPage() {
                 )}
               </strong>
             </div>
+            <div>
+              <span className="meta-label">Proofs Open</span>
+              <strong>{formatDateTime(item.startAt)}</strong>
+            </div>
             <div>
               <span className="meta-label">Deadline</span>
               <strong>{formatDateTime(item.deadline)}</strong>
@@ -263,14 +312,38 @@ export default function CommitmentDetailPage() {
             </div>
           </div>
 
+          {item.metadataUri ? (
+            <div>
+              <span className="meta-label">Success Criteria</span>
+              <p>
+                {item.metadataUri.startsWith('http') ? (
+                  <a href={item.metadataUri} target="_blank" rel="noreferrer">
+                    {item.metadataUri}
+                  </a>
+                ) : (
+                  <code>{item.metadataUri}</code>
+                )}
+              </p>
+              <p>
+                <span className="meta-label">Metadata Hash</span>
+                <code>{item.metadataHash}</code>
+              </p>
+            </div>
+          ) : null}
+
           <div>
             <span className="meta-label">Proof URL</span>
-            {item.proofUrl ? (
+            {item.proofUrl &&
+            (item.proofKind === ProofKind.HttpUrl || item.proofKind === ProofKind.AttestedUrl) ? (
               <p>
                 <a href={item.proofUrl} target="_blank" rel="noreferrer">
                   {item.proofUrl}
                 </a>
               </p>
+            ) : item.proofUrl ? (
+              <p>
+                <code>{item.proofUrl}</code>
+              </p>
             ) : (
               <p>Not submitted</p>
             )}
@@ -295,9 +368,23 @@ export default function CommitmentDetailPage() {
               </button>
             ) : null}
 
+            {eligibility.canForgive ? (
+              <button className="btn btn-secondary" disabled={submitting} onClick={onForgive}>
+                Forgive Stake
+              </button>
+            ) : null}
+
+            {eligibility.canSweep ? (
+              <button className="btn btn-secondary" disabled={submitting} onClick={onSweep}>
+                Sweep to Fallback
+              </button>
+            ) : null}
+
             {eligibility.canCancel ? (
               <button className="btn btn-secondary" disabled={submitting} onClick={onCancel}>
-                Cancel
+                {eligibility.cancelPenaltyBps > 0
+                  ? `Cancel (${eligibility.cancelPenaltyBps / 100}% penalty)`
+                  : 'Cancel'}
               </button>
             ) : null}
 
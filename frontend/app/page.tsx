@@ -136,7 +136,7 @@ export default function HomePage() {
           </section>
 
           <section className="section">
-            <h2>Failed / Claimed ({board.failed.length})</h2>
+            <h2>Failed / Claimed / Forgiven ({board.failed.length})</h2>
             {board.failed.length === 0 ? (
               <p className="empty-state">No failed commitments yet.</p>
             ) : (
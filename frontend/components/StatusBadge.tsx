@@ -6,6 +6,11 @@ const STATUS_LABELS: Record<CommitmentStatus, string> = {
   [CommitmentStatus.Failed]: 'Failed',
   [CommitmentStatus.Refunded]: 'Refunded',
   [CommitmentStatus.Claimed]: 'Claimed',
+  [CommitmentStatus.Swept]: 'Swept',
+  [CommitmentStatus.Forgiven]: 'Forgiven',
+  [CommitmentStatus.Sealed]: 'Sealed',
+  [CommitmentStatus.Verifying]: 'Verifying',
+  [CommitmentStatus.Releasing]: 'Releasing',
 };
 
 const STATUS_CLASSES: Record<CommitmentStatus, string> = {
@@ -14,6 +19,11 @@ const STATUS_CLASSES: Record<CommitmentStatus, string> = {
   [CommitmentStatus.Failed]: 'badge badge-failed',
   [CommitmentStatus.Refunded]: 'badge badge-refunded',
   [CommitmentStatus.Claimed]: 'badge badge-claimed',
+  [CommitmentStatus.Swept]: 'badge badge-swept',
+  [CommitmentStatus.Forgiven]: 'badge badge-forgiven',
+  [CommitmentStatus.Sealed]: 'badge badge-sealed',
+  [CommitmentStatus.Verifying]: 'badge badge-verifying',
+  [CommitmentStatus.Releasing]: 'badge badge-releasing',
 };
 
 export function StatusBadge({ status }: { status: CommitmentStatus }) {
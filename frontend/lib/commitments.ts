@@ -4,11 +4,21 @@ import {
   CommitmentStatus,
 } from '../types';
 
+// Mirrors the contract's reveal window for sealed proofs.
+const REVEAL_WINDOW_SECONDS = 86_400;
+
 export function commitmentBucket(status: CommitmentStatus): CommitmentBucket {
-  if (status === CommitmentStatus.Active) return 'active';
+  if (
+    status === CommitmentStatus.Active ||
+    status === CommitmentStatus.Sealed ||
+    status === CommitmentStatus.Verifying
+  ) {
+    return 'active';
+  }
   if (status === CommitmentStatus.Completed || status === CommitmentStatus.Refunded) {
     return 'completed';
   }
+  // Forgiven commitments failed; the recipient only waived the stake.
   return 'failed';
 }
 
@@ -49,7 +59,11 @@ export interface CommitmentEligibility {
   canSubmitProof: boolean;
   canFinalize: boolean;
   canClaim: boolean;
+  canForgive: boolean;
+  canSweep: boolean;
   canCancel: boolean;
+  // Penalty a cancellation right now would send to the recipient, in basis points.
+  cancelPenaltyBps: number;
 }
 
 export function getCommitmentEligibility(
@@ -64,15 +78,36 @@ export function getCommitmentEligibility(
   const isActive = commitment.status === CommitmentStatus.Active;
   const isCompleted = commitment.status === CommitmentStatus.Completed;
   const isFailed = commitment.status === CommitmentStatus.Failed;
+  const isSealed = commitment.status === CommitmentStatus.Sealed;
+
+  // A sealed proof can still be revealed until the reveal window closes.
+  const settleAfter = isSealed ? commitment.deadline + REVEAL_WINDOW_SECONDS : commitment.deadline;
 
-  const canSubmitProof = isCreator && isActive && now <= commitment.deadline;
-  const canFinalize = (isActive || isCompleted) && now > commitment.deadline;
+  // Proofs are accepted from `startAt` and can be revised until the deadline.
+  // Like `submit_proof`, refused when an attester or oracle decides instead.
+  const isSelfReported = !commitment.oracle && !commitment.attester;
+  const canSubmitProof =
+    isCreator &&
+    isSelfReported &&
+    (isActive || isCompleted) &&
+    now >= commitment.startAt &&
+    now <= commitment.deadline;
+  const canFinalize = (isActive || isCompleted || isSealed) && now > settleAfter;
+  // Lapsed commitments nobody finalized are settled by `claim`, cooldown counted from `settleAfter`.
+  const isExpiredActive = (isActive || isSealed) && now > settleAfter;
+  const cooldownStart = commitment.finalizedAt > 0 ? commitment.finalizedAt : settleAfter;
+  const claimTime = cooldownStart + commitment.cooldownSeconds;
   const canClaim =
     isRecipient &&
-    isFailed &&
-    commitment.finalizedAt > 0 &&
-    now >= (commitment.finalizedAt + commitment.cooldownSeconds);
-  const canCancel = isCreator && isActive && now < commitment.deadline;
+    (isFailed || isExpiredActive) &&
+    now >= claimTime &&
+    now < claimTime + commitment.claimWindowSeconds;
+  const canForgive = isRecipient && isFailed;
+  const canSweep =
+    (isFailed || isExpiredActive) && now >= claimTime + commitment.claimWindowSeconds;
+  const canCancel = isCreator && isActive && commitment.cancellable && now < commitment.deadline;
+  const cancelPenaltyBps =
+    now <= commitment.createdAt + commitment.cancelGraceSeconds ? 0 : commitment.cancelPenaltyBps;
 
   return {
     isCreator,
@@ -80,6 +115,9 @@ export function getCommitmentEligibility(
     canSubmitProof,
     canFinalize,
     canClaim,
+    canForgive,
+    canSweep,
     canCancel,
+    cancelPenaltyBps,
   };
 }
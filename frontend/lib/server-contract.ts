@@ -3,7 +3,7 @@ import 'server-only';
 import { ApiNetworkProvider } from '@multiversx/sdk-network-providers';
 import { getContract, getResultsParser } from '@/lib/contract';
 import { apiBaseUrl } from '@/config';
-import { Commitment, CommitmentStatus } from '@/types';
+import { Commitment, CommitmentStatus, ProofKind } from '@/types';
 
 function toNumber(value: unknown): number {
   if (typeof value === 'number') return Number.isFinite(value) ? value : 0;
@@ -52,6 +52,15 @@ function toAddress(value: unknown): string {
   return '';
 }
 
+// The contract stores an unset oracle or attester as the zero address.
+function toOptionalAddress(value: unknown): string {
+  if (value && typeof value === 'object') {
+    const candidate = value as { hex?: () => string };
+    if (candidate.hex && /^0*$/.test(candidate.hex())) return '';
+  }
+  return toAddress(value);
+}
+
 function toUtf8(value: unknown): string {
   if (Buffer.isBuffer(value)) return value.toString('utf8');
   if (value instanceof Uint8Array) return Buffer.from(value).toString('utf8');
@@ -59,6 +68,10 @@ function toUtf8(value: unknown): string {
   return '';
 }
 
+function toUtf8List(value: unknown): string[] {
+  return Array.isArray(value) ? value.map(toUtf8) : [];
+}
+
 function toHex(value: unknown): string {
   if (Buffer.isBuffer(value)) return value.toString('hex');
   if (value instanceof Uint8Array) return Buffer.from(value).toString('hex');
@@ -66,6 +79,26 @@ function toHex(value: unknown): string {
   return '';
 }
 
+// Named ABI enums decode to `{ name, fields }`.
+function toEnumName(value: unknown): string | undefined {
+  if (value && typeof value === 'object' && 'name' in value) {
+    return (value as { name: string }).name;
+  }
+  return undefined;
+}
+
+function toStatus(value: unknown): CommitmentStatus {
+  const name = toEnumName(value) as keyof typeof CommitmentStatus | undefined;
+  if (name && name in CommitmentStatus) return CommitmentStatus[name];
+  return toNumber(value) as CommitmentStatus;
+}
+
+function toProofKind(value: unknown): ProofKind {
+  const name = toEnumName(value) as keyof typeof ProofKind | undefined;
+  if (name && name in ProofKind) return ProofKind[name];
+  return toNumber(value) as ProofKind;
+}
+
 interface ValueContainer {
   valueOf(): unknown;
 }
@@ -85,12 +118,26 @@ function parseCommitmentRaw(raw: Record<string, unknown>): Commitment {
     deadline: toNumber(raw.deadline),
     cooldownSeconds: toNumber(raw.cooldown_seconds),
     createdAt: toNumber(raw.created_at),
-    status: toNumber(raw.status) as CommitmentStatus,
+    status: toStatus(raw.status),
     title: toUtf8(raw.title),
     proofUrl: toUtf8(raw.proof_url),
     proofHash: toHex(raw.proof_hash),
     proofSubmittedAt: toNumber(raw.proof_submitted_at),
     finalizedAt: toNumber(raw.finalized_at),
+    fallback: toAddress(raw.fallback),
+    claimWindowSeconds: toNumber(raw.claim_window_seconds),
+    forgivenAmount: toIntegerString(raw.forgiven_amount),
+    proofKind: toProofKind(raw.proof_kind),
+    oracle: toOptionalAddress(raw.oracle),
+    attester: toOptionalAddress(raw.attester),
+    startAt: toNumber(raw.start_at),
+    cancelGraceSeconds: toNumber(raw.cancel_grace_seconds),
+    cancelPenaltyBps: toNumber(raw.cancel_penalty_bps),
+    cancellable: Boolean(raw.cancellable),
+    tags: toUtf8List(raw.tags),
+    description: toUtf8(raw.description),
+    metadataUri: toUtf8(raw.metadata_uri),
+    metadataHash: toHex(raw.metadata_hash),
   };
 }
 
@@ -59,14 +59,25 @@ export function buildCreateCommitmentPayload(params: {
   cooldownSeconds?: number;
 }): DappTransactionPayload {
   const contract = getContract();
-  const args: Array<string | number> = [
-    params.title,
-    params.recipient,
-    params.deadline,
-  ];
+  const args: unknown[] = [params.title, params.recipient, params.deadline];
 
+  // `CreateOptions`: every field is an Option, null leaves the contract default.
   if (typeof params.cooldownSeconds === 'number') {
-    args.push(params.cooldownSeconds);
+    args.push({
+      cooldown_seconds: params.cooldownSeconds,
+      claim_window_seconds: null,
+      fallback: null,
+      oracle: null,
+      attester: null,
+      start_at: null,
+      cancel_grace_seconds: null,
+      cancel_penalty_bps: null,
+      cancellable: null,
+      tags: null,
+      description: null,
+      metadata_uri: null,
+      metadata_hash: null,
+    });
   }
 
   const interaction = contract.methods.create_commitment(args);
@@ -129,6 +140,45 @@ export function buildClaimPayload(params: {
   });
 }
 
+export function buildForgivePayload(params: {
+  sender: string;
+  chainId: string;
+  id: number;
+  amountWei?: string;
+}): DappTransactionPayload {
+  const contract = getContract();
+  const args: Array<string | number> = [params.id];
+
+  if (params.amountWei) {
+    args.push(params.amountWei);
+  }
+
+  const interaction = contract.methods.forgive(args);
+
+  return interactionToPayload({
+    interaction,
+    sender: params.sender,
+    chainId: params.chainId,
+    gasLimit: 15_000_000n,
+  });
+}
+
+export function buildSweepPayload(params: {
+  sender: string;
+  chainId: string;
+  id: number;
+}): DappTransactionPayload {
+  const contract = getContract();
+  const interaction = contract.methods.sweep([params.id]);
+
+  return interactionToPayload({
+    interaction,
+    sender: params.sender,
+    chainId: params.chainId,
+    gasLimit: 15_000_000n,
+  });
+}
+
 export function buildCancelPayload(params: {
   sender: string;
   chainId: string;
@@ -8,7 +8,7 @@ import {
 } from '../lib/commitments';
 import { queryCommitments } from '../lib/commitment-query';
 import { validateCreateCommitmentInput } from '../lib/create-commitment-validation';
-import { Commitment, CommitmentStatus } from '../types';
+import { Commitment, CommitmentStatus, ProofKind } from '../types';
 
 const base: Commitment = {
   id: 1,
@@ -24,14 +24,30 @@ const base: Commitment = {
   proofHash: '',
   proofSubmittedAt: 0,
   finalizedAt: 0,
+  fallback: 'erd1creator',
+  claimWindowSeconds: 1000,
+  forgivenAmount: '0',
+  proofKind: ProofKind.HttpUrl,
+  oracle: '',
+  attester: '',
+  startAt: 100,
+  cancelGraceSeconds: 100,
+  cancelPenaltyBps: 1000,
+  cancellable: true,
+  tags: [],
+  description: '',
+  metadataUri: '',
+  metadataHash: '',
 };
 
 test('commitmentBucket maps statuses', () => {
   assert.equal(commitmentBucket(CommitmentStatus.Active), 'active');
+  assert.equal(commitmentBucket(CommitmentStatus.Sealed), 'active');
   assert.equal(commitmentBucket(CommitmentStatus.Completed), 'completed');
   assert.equal(commitmentBucket(CommitmentStatus.Refunded), 'completed');
   assert.equal(commitmentBucket(CommitmentStatus.Failed), 'failed');
   assert.equal(commitmentBucket(CommitmentStatus.Claimed), 'failed');
+  assert.equal(commitmentBucket(CommitmentStatus.Forgiven), 'failed');
 });
 
 test('eligibility respects deadline and cooldown boundaries', () => {
@@ -40,6 +56,19 @@ test('eligibility respects deadline and cooldown boundaries', () => {
   assert.equal(e1.canSubmitProof, true);
   assert.equal(e1.canFinalize, false);
 
+  const notStarted = { ...active, startAt: 300 };
+  assert.equal(getCommitmentEligibility(notStarted, 'erd1creator', 299).canSubmitProof, false);
+  assert.equal(getCommitmentEligibility(notStarted, 'erd1creator', 300).canSubmitProof, true);
+
+  const completed = { ...active, status: CommitmentStatus.Completed };
+  assert.equal(getCommitmentEligibility(completed, 'erd1creator', 500).canSubmitProof, true);
+  assert.equal(getCommitmentEligibility(completed, 'erd1creator', 501).canSubmitProof, false);
+
+  const attested = { ...active, attester: 'erd1attester' };
+  assert.equal(getCommitmentEligibility(attested, 'erd1creator', 500).canSubmitProof, false);
+  const oracleVerified = { ...completed, oracle: 'erd1oracle' };
+  assert.equal(getCommitmentEligibility(oracleVerified, 'erd1creator', 500).canSubmitProof, false);
+
   const e2 = getCommitmentEligibility(active, 'erd1creator', 501);
   assert.equal(e2.canSubmitProof, false);
   assert.equal(e2.canFinalize, true);
@@ -55,6 +84,20 @@ test('eligibility respects deadline and cooldown boundaries', () => {
 
   const onBoundaryClaim = getCommitmentEligibility(failed, 'erd1recipient', 1_020);
   assert.equal(onBoundaryClaim.canClaim, true);
+  assert.equal(onBoundaryClaim.canForgive, true);
+  assert.equal(getCommitmentEligibility(failed, 'erd1creator', 1_020).canForgive, false);
+
+  const sealed = { ...active, status: CommitmentStatus.Sealed };
+  assert.equal(getCommitmentEligibility(sealed, 'erd1creator', 501).canFinalize, false);
+  assert.equal(getCommitmentEligibility(sealed, 'erd1creator', 86_901).canFinalize, true);
+
+  const unfinalized = { ...active, cooldownSeconds: 20 };
+  assert.equal(getCommitmentEligibility(unfinalized, 'erd1recipient', 519).canClaim, false);
+  assert.equal(getCommitmentEligibility(unfinalized, 'erd1recipient', 520).canClaim, true);
+
+  const expired = getCommitmentEligibility(failed, 'erd1recipient', 2_020);
+  assert.equal(expired.canClaim, false);
+  assert.equal(expired.canSweep, true);
 });
 
 test('sortCommitments sorts active by nearest deadline', () => {
@@ -95,6 +138,16 @@ test('queryCommitments paginates active commitments and tracks next cursor', ()
   assert.deepEqual(page2.items.map((item) => item.id), [3]);
 });
 
+test('cancellation is free within the grace period and can be disabled', () => {
+  const active = { ...base, status: CommitmentStatus.Active, deadline: 500 };
+  assert.equal(getCommitmentEligibility(active, 'erd1creator', 200).cancelPenaltyBps, 0);
+  assert.equal(getCommitmentEligibility(active, 'erd1creator', 201).cancelPenaltyBps, 1000);
+  assert.equal(getCommitmentEligibility(active, 'erd1creator', 201).canCancel, true);
+
+  const locked = { ...active, cancellable: false };
+  assert.equal(getCommitmentEligibility(locked, 'erd1creator', 150).canCancel, false);
+});
+
 test('validateCreateCommitmentInput enforces deadline and cooldown boundaries', () => {
   const valid = validateCreateCommitmentInput({
     title: 'Ship docs',
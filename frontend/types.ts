@@ -1,9 +1,25 @@
+// Mirrors the contract's `CommitmentStatus` ABI enum; variant names must match.
 export enum CommitmentStatus {
   Active = 0,
   Completed = 1,
   Failed = 2,
   Refunded = 3,
   Claimed = 4,
+  Swept = 5,
+  Forgiven = 6,
+  Sealed = 7,
+  Verifying = 8,
+  Releasing = 9,
+}
+
+// Mirrors the contract's `ProofKind` ABI enum; variant names must match.
+export enum ProofKind {
+  HttpUrl = 0,
+  IpfsCid = 1,
+  ContentHash = 2,
+  TxHash = 3,
+  OracleAttestation = 4,
+  AttestedUrl = 5,
 }
 
 export type CommitmentBucket = 'active' | 'completed' | 'failed';
@@ -22,6 +38,21 @@ export interface Commitment {
   proofHash: string;
   proofSubmittedAt: number;
   finalizedAt: number;
+  fallback: string;
+  claimWindowSeconds: number;
+  forgivenAmount: string;
+  proofKind: ProofKind;
+  // Empty when unset.
+  oracle: string;
+  attester: string;
+  startAt: number;
+  cancelGraceSeconds: number;
+  cancelPenaltyBps: number;
+  cancellable: boolean;
+  tags: string[];
+  description: string;
+  metadataUri: string;
+  metadataHash: string;
 }
 
 export interface CommitmentListResponse {